@@ -1,5 +1,8 @@
-use crate::{config::Config, errors::Error, time};
+use crate::{color, config::Config, errors::Error, time, todoist};
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use tokio::fs;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub struct Comment {
@@ -10,7 +13,12 @@ pub struct Comment {
     pub is_deleted: bool,
     pub posted_at: String,
     pub reactions: Option<Reactions>,
-    pub item_id: String,
+    /// The task the comment is on, when it is not a project comment
+    #[serde(default)]
+    pub item_id: Option<String>,
+    /// The project the comment is on, when it is not a task comment
+    #[serde(default)]
+    pub project_id: Option<String>,
     pub file_attachment: Option<Attachment>,
 }
 
@@ -87,45 +95,168 @@ pub struct ImageAttachment {
     pub tn_l: Option<serde_json::Value>,
 }
 
-impl Comment {
-    pub fn fmt(&self, config: &Config) -> Result<String, Error> {
-        let timezone = config.get_timezone()?;
-        let timezone = time::timezone_from_str(&timezone)?;
-        let datetime = time::datetime_from_str(&self.posted_at, timezone)?;
-        let formatted_date = time::datetime_to_string(&datetime, config)?;
+/// Used for images when the URL does not end in a file name
+const IMAGE_FILE_NAME: &str = "image";
+const IMAGE_RESOURCE_TYPE: &str = "image";
 
-        let link = match &self.file_attachment {
-            None => String::new(),
-            Some(Attachment::Url(UrlAttachment {
-                url,
-                site_name,
-                title,
-                ..
-            })) => Self::render_link(url, &format!("{site_name}: {title}")),
-            Some(Attachment::ShortUrl(ShortUrlAttachment { url, title, .. })) => {
-                Self::render_link(url, title)
-            }
-            Some(Attachment::Video(VideoAttachment {
-                url,
-                site_name,
-                title,
-                ..
-            })) => Self::render_link(url, &format!("{site_name}: {title}")),
-            Some(Attachment::File(FileAttachment {
-                file_url,
+/// The last segment of an image URL, i.e. "photo.jpg"
+fn image_file_name(url: &str) -> String {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(IMAGE_FILE_NAME)
+        .to_string()
+}
+
+/// Save the files and images attached to comments into a directory, keeping their names
+pub async fn download_attachments(
+    config: &Config,
+    comments: &[Comment],
+    dir: &Path,
+) -> Result<String, Error> {
+    let downloads = comments
+        .iter()
+        .filter_map(|comment| comment.file_attachment.as_ref())
+        .filter_map(Attachment::download)
+        .collect::<Vec<(String, &str)>>();
+    if downloads.is_empty() {
+        return Ok("No files or images to download".into());
+    }
+
+    fs::create_dir_all(dir).await?;
+    let mut paths = Vec::new();
+    for (file_name, url) in downloads {
+        let contents = todoist::download_file(config, url).await?;
+        let path = unique_path(dir, &file_name);
+        fs::write(&path, contents).await?;
+        paths.push(format!("- {}", path.display()));
+    }
+
+    Ok(format!(
+        "Downloaded {} attachment(s)\n{}",
+        paths.len(),
+        paths.join("\n")
+    ))
+}
+
+/// A path in the directory for the file name that does not overwrite an existing file, i.e. "log (1).txt"
+fn unique_path(dir: &Path, file_name: &str) -> PathBuf {
+    // Never let a file name from the API point outside of the directory
+    let file_name = Path::new(file_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(IMAGE_FILE_NAME);
+    let path = dir.join(file_name);
+    if !path.exists() {
+        return path;
+    }
+
+    let stem = Path::new(file_name)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(file_name);
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str());
+    let mut number = 1;
+    loop {
+        let path = match extension {
+            Some(extension) => dir.join(format!("{stem} ({number}).{extension}")),
+            None => dir.join(format!("{stem} ({number})")),
+        };
+        if !path.exists() {
+            return path;
+        }
+        number += 1;
+    }
+}
+
+/// MIME type to upload a file with, based on its extension
+pub fn file_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+    match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        Some("pdf") => "application/pdf",
+        Some("json") => "application/json",
+        Some("zip") => "application/zip",
+        Some("csv") => "text/csv",
+        Some("md") => "text/markdown",
+        Some("txt" | "log") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+impl Attachment {
+    pub fn url(&self) -> &str {
+        match self {
+            Attachment::File(FileAttachment { file_url, .. }) => file_url,
+            Attachment::Url(UrlAttachment { url, .. })
+            | Attachment::ShortUrl(ShortUrlAttachment { url, .. })
+            | Attachment::Video(VideoAttachment { url, .. })
+            | Attachment::Image(ImageAttachment { url, .. }) => url,
+        }
+    }
+
+    /// File name and URL for attachments that are files rather than links to web pages
+    pub fn download(&self) -> Option<(String, &str)> {
+        match self {
+            Attachment::File(FileAttachment {
                 file_name,
+                file_url,
                 ..
-            })) => Self::render_link(file_url, file_name),
-            Some(Attachment::Image(ImageAttachment {
+            }) => Some((file_name.clone(), file_url)),
+            Attachment::Image(ImageAttachment { url, .. }) => Some((image_file_name(url), url)),
+            // Images also match the fields of a short URL, which comes first when deserializing
+            Attachment::ShortUrl(ShortUrlAttachment {
+                resource_type, url, ..
+            }) if resource_type == IMAGE_RESOURCE_TYPE => Some((image_file_name(url), url)),
+            Attachment::Url(_) | Attachment::ShortUrl(_) | Attachment::Video(_) => None,
+        }
+    }
+
+    /// Human readable name for the attachment, i.e. "YouTube: Test Video"
+    pub fn label(&self) -> String {
+        match self {
+            Attachment::File(FileAttachment { file_name, .. }) => file_name.clone(),
+            Attachment::Url(UrlAttachment {
+                site_name, title, ..
+            })
+            | Attachment::Video(VideoAttachment {
+                site_name, title, ..
+            }) => format!("{site_name}: {title}"),
+            Attachment::ShortUrl(ShortUrlAttachment { title, .. }) => title.clone(),
+            Attachment::Image(ImageAttachment {
                 url,
                 site_name,
                 title,
                 ..
-            })) => {
+            }) => {
                 let site = site_name.as_deref().unwrap_or("Image");
                 let title = title.as_deref().unwrap_or(url);
-                Self::render_link(url, &format!("{site}: {title}"))
+                format!("{site}: {title}")
             }
+        }
+    }
+}
+
+impl Comment {
+    pub fn fmt(&self, config: &Config) -> Result<String, Error> {
+        let timezone = config.get_timezone()?;
+        let timezone = time::timezone_from_str(&timezone)?;
+        let datetime = time::datetime_from_str(&self.posted_at, timezone)?;
+        let formatted_date = time::datetime_to_string(&datetime, config)?;
+
+        let link = match &self.file_attachment {
+            None => String::new(),
+            Some(attachment) => Self::render_link(attachment.url(), &attachment.label()),
         };
 
         Ok(format!(
@@ -139,6 +270,35 @@ impl Comment {
     }
 }
 
+impl Display for Comment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let first_line = self.content.lines().next().unwrap_or_default();
+        match (&self.file_attachment, first_line) {
+            (Some(attachment), "") => write!(f, "{}", attachment.label()),
+            _ => write!(f, "{first_line}"),
+        }
+    }
+}
+
+/// Render every comment in full, oldest first, with the IDs needed to edit or delete them
+pub fn render_all(config: &Config, comments: &[Comment]) -> Result<String, Error> {
+    if comments.is_empty() {
+        return Ok("No comments".into());
+    }
+    let comment_icon = color::purple_string("★");
+    let comments = comments
+        .iter()
+        .map(|comment| {
+            Ok(format!(
+                "{comment_icon} {}\n{}",
+                comment.id,
+                comment.fmt(config)?
+            ))
+        })
+        .collect::<Result<Vec<String>, Error>>()?;
+    Ok(comments.join("\n\n"))
+}
+
 pub fn json_to_comment_response(json: String) -> Result<CommentResponse, Error> {
     let response: CommentResponse = serde_json::from_str(&json)?;
     Ok(response)
@@ -261,6 +421,105 @@ mod tests {
         assert!(output.contains("Just a plain comment"));
     }
 
+    #[tokio::test]
+    async fn test_render_all() {
+        let config = fixtures::config().await.with_timezone("UTC");
+        let comments = vec![
+            fixtures::comment(),
+            Comment {
+                id: "2".into(),
+                content: "Second line\nof the log".into(),
+                ..fixtures::comment()
+            },
+        ];
+
+        let output = render_all(&config, &comments).unwrap();
+        let ids = output
+            .lines()
+            .filter(|line| line.starts_with('★'))
+            .collect::<Vec<&str>>();
+        assert_eq!(ids, vec!["★ 2992679862", "★ 2"]);
+        assert!(output.ends_with("Second line\nof the log"));
+        assert_eq!(render_all(&config, &[]).unwrap(), "No comments");
+    }
+
+    #[tokio::test]
+    async fn test_download_attachments() {
+        let mut server = mockito::Server::new_async().await;
+        let file_mock = server
+            .mock("GET", "/file.pdf")
+            .with_status(200)
+            .with_body("pdf contents")
+            .create_async()
+            .await;
+        let image_mock = server
+            .mock("GET", "/image.jpg")
+            .with_status(200)
+            .with_body("jpg contents")
+            .create_async()
+            .await;
+
+        let config = fixtures::config().await.with_mock_url(server.url());
+        let dir = std::env::temp_dir().join(format!("tod-attachments-{}", std::process::id()));
+        // Attachments are downloaded from wherever they are hosted, so point them at the mock server
+        let comments = load_comments()
+            .await
+            .into_iter()
+            .map(|comment| {
+                let json = serde_json::to_string(&comment)
+                    .unwrap()
+                    .replace("https://example.com", &server.url());
+                serde_json::from_str(&json).unwrap()
+            })
+            .collect::<Vec<Comment>>();
+
+        let output = download_attachments(&config, &comments, &dir)
+            .await
+            .unwrap();
+        assert!(output.starts_with("Downloaded 2 attachment(s)"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("file.pdf")).unwrap(),
+            "pdf contents"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("image.jpg")).unwrap(),
+            "jpg contents"
+        );
+        file_mock.assert();
+        image_mock.assert();
+
+        assert_eq!(unique_path(&dir, "file.pdf"), dir.join("file (1).pdf"));
+        assert_eq!(unique_path(&dir, "../../file.txt"), dir.join("file.txt"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_type() {
+        assert_eq!(file_type(Path::new("screenshot.PNG")), "image/png");
+        assert_eq!(file_type(Path::new("logs/server.log")), "text/plain");
+        assert_eq!(file_type(Path::new("archive")), "application/octet-stream");
+    }
+
+    #[tokio::test]
+    async fn test_display() {
+        let comment = Comment {
+            content: "First line\nSecond line".into(),
+            ..fixtures::comment()
+        };
+        assert_eq!(comment.to_string(), "First line");
+
+        let file = load_comments()
+            .await
+            .into_iter()
+            .find(|c| c.id == "file-1")
+            .unwrap();
+        let file = Comment {
+            content: String::new(),
+            ..file
+        };
+        assert_eq!(file.to_string(), "file.pdf");
+    }
+
     /// Test with inline JSON to simulate the behavior of excluding comments
     /// This needs to be updated to work with the actual Regex and Mockito setup
     #[tokio::test]
@@ -2,9 +2,11 @@ use crate::cargo::Version;
 use crate::errors::Error;
 use crate::id::Resource;
 use crate::input::page_size;
+use crate::output::OutputFormat;
 use crate::projects::{LegacyProject, Project};
 use crate::tasks::Task;
 use crate::tasks::format::maybe_format_url;
+use crate::templates::Template;
 use crate::time::{SystemTimeProvider, TimeProviderEnum};
 use crate::{VERSION, cargo, color, debug, input, oauth, time, todoist};
 use inquire::Confirm;
@@ -12,6 +14,7 @@ use rand::distr::{Alphanumeric, SampleString};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::path::PathBuf;
 use terminal_size::{Height, Width, terminal_size};
@@ -95,6 +98,8 @@ pub struct Config {
     /// Goes straight to natural language input in datetime selection
     pub natural_language_only: Option<bool>,
     pub sort_value: Option<SortValue>,
+    /// Named bundles of tasks to create with the template command
+    pub templates: Option<BTreeMap<String, Template>>,
 
     /// For storing arguments from the commandline
     #[serde(skip)]
@@ -115,6 +120,7 @@ fn bell_on_failure() -> bool {
 pub struct Args {
     pub verbose: bool,
     pub timeout: Option<u64>,
+    pub output: OutputFormat,
 }
 #[derive(Default, Clone, Debug)]
 pub struct Internal {
@@ -166,6 +172,16 @@ impl Config {
             ..self.clone()
         }
     }
+    /// Set output format from the commandline on Config struct only
+    pub fn with_output(self: &Config, output: OutputFormat) -> Config {
+        Config {
+            args: Args {
+                output,
+                ..self.args.clone()
+            },
+            ..self.clone()
+        }
+    }
     /// Set token on Config struct only
     pub fn with_token(self: &Config, token: &str) -> Config {
         Config {
@@ -424,6 +440,7 @@ impl Config {
             args: Args {
                 verbose: false,
                 timeout: None,
+                output: OutputFormat::Text,
             },
             legacy_projects: Some(Vec::new()),
             time_provider: TimeProviderEnum::System(SystemTimeProvider),
@@ -431,6 +448,7 @@ impl Config {
             task_create_command: None,
             task_complete_command: None,
             projects: Some(Vec::new()),
+            templates: None,
         })
     }
 
@@ -465,6 +483,27 @@ impl Config {
         self.projects = Some(projects);
     }
 
+    /// Find a template by name
+    pub fn template(&self, name: &str) -> Result<&Template, Error> {
+        let templates = self.templates.as_ref();
+        templates
+            .and_then(|templates| templates.get(name))
+            .ok_or_else(|| {
+                let names = templates
+                    .map(|templates| templates.keys().cloned().collect::<Vec<String>>())
+                    .unwrap_or_default();
+                let available = if names.is_empty() {
+                    String::from("There are no templates in the config")
+                } else {
+                    format!("Available templates: {}", names.join(", "))
+                };
+                Error::new(
+                    "template",
+                    &format!("Could not find template '{name}'. {available}"),
+                )
+            })
+    }
+
     pub fn set_next_task(&self, task: Task) -> Config {
         let next_task: Option<Task> = Some(task);
 
@@ -589,10 +628,12 @@ impl Default for Config {
             args: Args {
                 verbose: false,
                 timeout: None,
+                output: OutputFormat::Text,
             },
             legacy_projects: Some(Vec::new()),
             time_provider: TimeProviderEnum::System(SystemTimeProvider),
             projects: Some(Vec::new()),
+            templates: None,
         }
     }
 }
@@ -653,7 +694,11 @@ pub async fn get_or_create(
     }?;
 
     let config = Config {
-        args: Args { timeout, verbose },
+        args: Args {
+            timeout,
+            verbose,
+            output: OutputFormat::Text,
+        },
         internal: Internal {
             tx: Some(tx.clone()),
         },
@@ -786,6 +831,7 @@ mod tests {
                 args: Args {
                     verbose: false,
                     timeout: None,
+                    output: OutputFormat::Text,
                 },
                 internal: Internal { tx: None },
                 sort_value: Some(SortValue::default()),
@@ -813,6 +859,7 @@ mod tests {
                 verbose: None,
                 no_sections: None,
                 natural_language_only: None,
+                templates: None,
             }
         }
         // Mock the url used for fetching projects and tasks
@@ -1020,11 +1067,13 @@ mod tests {
         let args = Args {
             verbose: true,
             timeout: Some(42),
+            output: OutputFormat::Json,
         };
         let args_debug = format!("{args:?}");
         assert!(args_debug.contains("Args"));
         assert!(args_debug.contains("verbose"));
         assert!(args_debug.contains("timeout"));
+        assert!(args_debug.contains("output"));
 
         let (tx, _rx) = unbounded_channel::<Error>();
         let internal = Internal { tx: Some(tx) };
@@ -1043,6 +1092,7 @@ mod tests {
         let args = Args {
             verbose: true,
             timeout: Some(10),
+            output: OutputFormat::Text,
         };
         let args_clone = args.clone();
         assert_eq!(args, args_clone);
@@ -1059,20 +1109,23 @@ mod tests {
             args,
             Args {
                 verbose: true,
-                timeout: Some(10)
+                timeout: Some(10),
+                output: OutputFormat::Text,
             }
         );
         assert_ne!(
             args,
             Args {
                 verbose: false,
-                timeout: Some(5)
+                timeout: Some(5),
+                output: OutputFormat::Text,
             }
         );
 
         let default_args = Args::default();
         assert_eq!(default_args.verbose, false);
         assert_eq!(default_args.timeout, None);
+        assert_eq!(default_args.output, OutputFormat::Text);
 
         let default_internal = Internal::default();
         assert!(default_internal.tx.is_none());
@@ -0,0 +1,240 @@
+use crate::{
+    config::Config,
+    errors::Error,
+    projects::Project,
+    sections::{self, Section},
+    tasks::{Duration, Task, Unit, format, priority::Priority},
+};
+
+pub mod ics;
+pub mod markdown;
+
+#[derive(clap::ValueEnum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// Comma separated values
+    Csv,
+    /// Tab separated values
+    Tsv,
+}
+
+#[derive(clap::ValueEnum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DocumentFormat {
+    /// Markdown with a heading per section and checklists for tasks
+    Markdown,
+}
+
+impl std::fmt::Display for DocumentFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocumentFormat::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportColumn {
+    Content,
+    Description,
+    Project,
+    Section,
+    Labels,
+    Priority,
+    Due,
+    Deadline,
+    Duration,
+    Url,
+}
+
+impl std::fmt::Display for ExportColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportColumn::Content => write!(f, "content"),
+            ExportColumn::Description => write!(f, "description"),
+            ExportColumn::Project => write!(f, "project"),
+            ExportColumn::Section => write!(f, "section"),
+            ExportColumn::Labels => write!(f, "labels"),
+            ExportColumn::Priority => write!(f, "priority"),
+            ExportColumn::Due => write!(f, "due"),
+            ExportColumn::Deadline => write!(f, "deadline"),
+            ExportColumn::Duration => write!(f, "duration"),
+            ExportColumn::Url => write!(f, "url"),
+        }
+    }
+}
+
+/// All columns in the order they are exported by default
+pub fn all_columns() -> Vec<ExportColumn> {
+    vec![
+        ExportColumn::Content,
+        ExportColumn::Description,
+        ExportColumn::Project,
+        ExportColumn::Section,
+        ExportColumn::Labels,
+        ExportColumn::Priority,
+        ExportColumn::Due,
+        ExportColumn::Deadline,
+        ExportColumn::Duration,
+        ExportColumn::Url,
+    ]
+}
+
+/// Render tasks as a table with a header row, suitable for pasting into a spreadsheet
+pub async fn table(
+    config: &Config,
+    tasks: &[Task],
+    format: &ExportFormat,
+    columns: &[ExportColumn],
+) -> Result<String, Error> {
+    let projects = config.projects().await?;
+    let sections = if columns.contains(&ExportColumn::Section) {
+        sections::sections_for_tasks(config, tasks).await?
+    } else {
+        Vec::new()
+    };
+
+    let header = columns
+        .iter()
+        .map(|column| escape(&column.to_string(), format))
+        .collect::<Vec<String>>();
+
+    let mut rows = vec![join(header, format)];
+    for task in tasks {
+        let row = columns
+            .iter()
+            .map(|column| escape(&cell(task, column, &projects, &sections), format))
+            .collect::<Vec<String>>();
+        rows.push(join(row, format));
+    }
+
+    Ok(rows.join("\n"))
+}
+
+fn cell(task: &Task, column: &ExportColumn, projects: &[Project], sections: &[Section]) -> String {
+    match column {
+        ExportColumn::Content => task.content.clone(),
+        ExportColumn::Description => task.description.clone(),
+        ExportColumn::Project => projects
+            .iter()
+            .find(|project| project.id == task.project_id)
+            .map(|project| project.name.clone())
+            .unwrap_or_default(),
+        ExportColumn::Section => sections
+            .iter()
+            .find(|section| Some(&section.id) == task.section_id.as_ref())
+            .map(|section| section.name.clone())
+            .unwrap_or_default(),
+        ExportColumn::Labels => task.labels.join(", "),
+        ExportColumn::Priority => priority(&task.priority),
+        ExportColumn::Due => task
+            .due
+            .as_ref()
+            .map(|due| due.date.clone())
+            .unwrap_or_default(),
+        ExportColumn::Deadline => task
+            .deadline
+            .as_ref()
+            .map(|deadline| deadline.date.clone())
+            .unwrap_or_default(),
+        ExportColumn::Duration => match &task.duration {
+            None => String::new(),
+            Some(Duration {
+                amount,
+                unit: Unit::Minute,
+            }) => format!("{amount} min"),
+            Some(Duration {
+                amount,
+                unit: Unit::Day,
+            }) => format!("{amount} day"),
+        },
+        ExportColumn::Url => format::task_url(&task.id),
+    }
+}
+
+/// Uses the priority names from the Todoist UI, without colors
+fn priority(priority: &Priority) -> String {
+    match priority {
+        Priority::None => "P4",
+        Priority::Low => "P3",
+        Priority::Medium => "P2",
+        Priority::High => "P1",
+    }
+    .into()
+}
+
+fn join(fields: Vec<String>, format: &ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => fields.join(","),
+        ExportFormat::Tsv => fields.join("\t"),
+    }
+}
+
+/// CSV fields are quoted when needed, TSV has no quoting so separators are replaced with spaces
+fn escape(field: &str, format: &ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv if field.contains([',', '"', '\n', '\r']) => {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        }
+        ExportFormat::Csv => field.to_string(),
+        ExportFormat::Tsv => field.replace(['\t', '\n', '\r'], " "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use crate::test::responses::ResponseFromFile;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain", &ExportFormat::Csv), "plain");
+        assert_eq!(escape("a, b", &ExportFormat::Csv), "\"a, b\"");
+        assert_eq!(
+            escape("say \"hi\"", &ExportFormat::Csv),
+            "\"say \"\"hi\"\"\""
+        );
+        assert_eq!(escape("a\tb\nc", &ExportFormat::Tsv), "a b c");
+    }
+
+    #[tokio::test]
+    async fn test_table() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/sections?project_id=123&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Sections.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = Task {
+            project_id: "123".into(),
+            section_id: Some("1234".into()),
+            labels: vec!["computer".into(), "work".into()],
+            description: "line one\nline two".into(),
+            ..test::fixtures::today_task().await
+        };
+        let due = task.due.clone().unwrap().date;
+        let deadline = task.deadline.clone().unwrap().date;
+
+        let csv = table(&config, &[task.clone()], &ExportFormat::Csv, &all_columns())
+            .await
+            .unwrap();
+        assert_eq!(
+            csv,
+            format!(
+                "content,description,project,section,labels,priority,due,deadline,duration,url\n\
+                TEST,\"line one\nline two\",myproject,Bread,\"computer, work\",P2,{due},{deadline},15 min,https://app.todoist.com/app/task/6Xqhv4cwxgjwG9w8"
+            )
+        );
+
+        let columns = vec![ExportColumn::Content, ExportColumn::Description];
+        let tsv = table(&config, &[task], &ExportFormat::Tsv, &columns)
+            .await
+            .unwrap();
+        assert_eq!(tsv, "content\tdescription\nTEST\tline one line two");
+        mock.expect(1);
+    }
+}
@@ -0,0 +1,257 @@
+use chrono::{Duration as ChronoDuration, NaiveDate, Utc};
+
+use crate::{
+    VERSION,
+    config::Config,
+    errors::Error,
+    tasks::{DateTimeInfo, Duration, Task, Unit, format},
+    time,
+};
+
+const FORMAT_UTC: &str = "%Y%m%dT%H%M%SZ";
+const FORMAT_ICS_DATE: &str = "%Y%m%d";
+/// RFC 5545 limits content lines to 75 octets
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Builds a VCALENDAR with one VEVENT per task that has a due date, returns the calendar and the number of events
+pub fn calendar(config: &Config, tasks: &[Task]) -> Result<(String, usize), Error> {
+    let stamp = time::datetime_now(config)?
+        .with_timezone(&Utc)
+        .format(FORMAT_UTC)
+        .to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//tod//tod {VERSION}//EN"),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    let mut count = 0;
+    for task in tasks {
+        if let Some(event) = event(config, task, &stamp)? {
+            lines.extend(event);
+            count += 1;
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let calendar = lines
+        .iter()
+        .map(|line| fold(line))
+        .collect::<Vec<String>>()
+        .join("\r\n");
+
+    Ok((format!("{calendar}\r\n"), count))
+}
+
+/// Tasks with a due datetime become timed events, date only tasks become all day events.
+/// Timed tasks without a duration end when they start, as some calendars drop events without an end.
+fn event(config: &Config, task: &Task, stamp: &str) -> Result<Option<Vec<String>>, Error> {
+    let (start, end) = match task.datetimeinfo(config)? {
+        DateTimeInfo::NoDateTime => return Ok(None),
+        DateTimeInfo::Date { date, .. } => {
+            let days = match task.duration {
+                Some(Duration {
+                    amount,
+                    unit: Unit::Day,
+                }) => amount.max(1),
+                _ => 1,
+            };
+            let end = date + ChronoDuration::days(days.into());
+            (
+                format!("DTSTART;VALUE=DATE:{}", ics_date(date)),
+                format!("DTEND;VALUE=DATE:{}", ics_date(end)),
+            )
+        }
+        DateTimeInfo::DateTime { datetime, .. } => {
+            let start = datetime.with_timezone(&Utc);
+            let end = match task.duration {
+                None => start,
+                Some(Duration {
+                    amount,
+                    unit: Unit::Minute,
+                }) => start + ChronoDuration::minutes(amount.into()),
+                Some(Duration {
+                    amount,
+                    unit: Unit::Day,
+                }) => start + ChronoDuration::days(amount.into()),
+            };
+            (
+                format!("DTSTART:{}", start.format(FORMAT_UTC)),
+                format!("DTEND:{}", end.format(FORMAT_UTC)),
+            )
+        }
+    };
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}@tod", task.id),
+        format!("DTSTAMP:{stamp}"),
+        start,
+        end,
+    ];
+    lines.push(format!("SUMMARY:{}", escape(&task.content)));
+
+    let description = description(task);
+    if !description.is_empty() {
+        lines.push(format!("DESCRIPTION:{}", escape(&description)));
+    }
+    lines.push(format!("URL:{}", format::task_url(&task.id)));
+    lines.push("END:VEVENT".to_string());
+
+    Ok(Some(lines))
+}
+
+fn description(task: &Task) -> String {
+    let mut parts = Vec::new();
+    if !task.description.is_empty() {
+        parts.push(task.description.clone());
+    }
+    if let Some(deadline) = &task.deadline {
+        parts.push(format!("Deadline: {}", deadline.date));
+    }
+    parts.join("\n\n")
+}
+
+fn ics_date(date: NaiveDate) -> String {
+    date.format(FORMAT_ICS_DATE).to_string()
+}
+
+/// Escape characters that have meaning in TEXT values
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Split long lines, continuation lines start with a single space
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for character in line.chars() {
+        let width = character.len_utf8();
+        if length + width > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(character);
+        length += width;
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::DateInfo;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a, b; c\\d\ne"), "a\\, b\\; c\\\\d\\ne");
+    }
+
+    #[test]
+    fn test_fold() {
+        let line = "x".repeat(160);
+        let folded = fold(&line);
+        let lines: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].len(), 75);
+        assert_eq!(lines[1].len(), 75);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(lines.concat().replace(' ', ""), line);
+    }
+
+    #[tokio::test]
+    async fn test_calendar() {
+        let config = test::fixtures::config().await;
+        let timed = Task {
+            id: "1".into(),
+            content: "Write report, part 1".into(),
+            description: String::new(),
+            deadline: None,
+            due: Some(DateInfo {
+                date: "2025-05-12T16:00:00Z".into(),
+                is_recurring: false,
+                string: "May 12 9am".into(),
+                lang: "en".into(),
+                timezone: None,
+            }),
+            duration: Some(Duration {
+                amount: 90,
+                unit: Unit::Minute,
+            }),
+            ..test::fixtures::today_task().await
+        };
+        let all_day = Task {
+            id: "2".into(),
+            content: "Pay rent".into(),
+            description: "Online".into(),
+            due: Some(DateInfo {
+                date: "2025-05-13".into(),
+                is_recurring: false,
+                string: "May 13".into(),
+                lang: "en".into(),
+                timezone: None,
+            }),
+            duration: None,
+            ..test::fixtures::today_task().await
+        };
+        let undated = Task {
+            id: "3".into(),
+            due: None,
+            ..test::fixtures::today_task().await
+        };
+        let deadline = all_day.deadline.clone().unwrap().date;
+
+        let (calendar, count) = calendar(&config, &[timed, all_day, undated]).unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            calendar,
+            format!(
+                "BEGIN:VCALENDAR\r\n\
+                VERSION:2.0\r\n\
+                PRODID:-//tod//tod {VERSION}//EN\r\n\
+                CALSCALE:GREGORIAN\r\n\
+                BEGIN:VEVENT\r\n\
+                UID:1@tod\r\n\
+                DTSTAMP:20250510T100000Z\r\n\
+                DTSTART:20250512T160000Z\r\n\
+                DTEND:20250512T173000Z\r\n\
+                SUMMARY:Write report\\, part 1\r\n\
+                URL:https://app.todoist.com/app/task/1\r\n\
+                END:VEVENT\r\n\
+                BEGIN:VEVENT\r\n\
+                UID:2@tod\r\n\
+                DTSTAMP:20250510T100000Z\r\n\
+                DTSTART;VALUE=DATE:20250513\r\n\
+                DTEND;VALUE=DATE:20250514\r\n\
+                SUMMARY:Pay rent\r\n\
+                DESCRIPTION:Online\\n\\nDeadline: {deadline}\r\n\
+                URL:https://app.todoist.com/app/task/2\r\n\
+                END:VEVENT\r\n\
+                END:VCALENDAR\r\n"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_event_without_duration() {
+        let config = test::fixtures::config().await;
+        let task = Task {
+            duration: None,
+            ..test::fixtures::today_task().await
+        };
+
+        let lines = event(&config, &task, "20250510T100000Z").unwrap().unwrap();
+
+        assert_eq!(lines[3], "DTSTART:20250510T120000Z");
+        assert_eq!(lines[4], "DTEND:20250510T120000Z");
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    config::Config,
+    errors::Error,
+    projects::Project,
+    sections::Section,
+    tasks::{self, DateTimeInfo, Task},
+    time::{self, FORMAT_DATE, FORMAT_DATE_AND_TIME},
+};
+
+/// Each level of subtasks is indented by this many spaces
+const INDENT: usize = 2;
+
+/// Render a project as a Markdown document with a heading per section and nested checklists for subtasks
+pub fn document(
+    config: &Config,
+    project: &Project,
+    sections: &[Section],
+    tasks: &[Task],
+) -> Result<String, Error> {
+    let mut sections = sections.to_vec();
+    sections.sort_by_key(|section| section.section_order);
+
+    let mut buffer = format!("# {}\n", project.name);
+    if !project.description.is_empty() {
+        buffer.push_str(&format!("\n{}\n", project.description));
+    }
+
+    let unsectioned = top_level(tasks)
+        .into_iter()
+        .filter(|task| {
+            task.section_id
+                .as_ref()
+                .is_none_or(|id| !sections.iter().any(|section| &section.id == id))
+        })
+        .collect::<Vec<&Task>>();
+    if !unsectioned.is_empty() {
+        buffer.push('\n');
+        buffer.push_str(&checklist(config, &unsectioned, tasks, 0)?);
+    }
+
+    for section in sections {
+        buffer.push_str(&format!("\n## {}\n", section.name));
+        let section_tasks = top_level(tasks)
+            .into_iter()
+            .filter(|task| task.section_id.as_ref() == Some(&section.id))
+            .collect::<Vec<&Task>>();
+        if !section_tasks.is_empty() {
+            buffer.push('\n');
+            buffer.push_str(&checklist(config, &section_tasks, tasks, 0)?);
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Tasks without a parent, or whose parent is not part of the export
+fn top_level(tasks: &[Task]) -> Vec<&Task> {
+    let mut top_level = tasks::top_level_tasks(tasks);
+    top_level.sort_by_key(|task| task.child_order);
+    top_level
+}
+
+fn checklist(
+    config: &Config,
+    items: &[&Task],
+    tasks: &[Task],
+    depth: usize,
+) -> Result<String, Error> {
+    let mut buffer = String::new();
+    for task in items {
+        buffer.push_str(&item(config, task, depth)?);
+        buffer.push_str(&checklist(
+            config,
+            &tasks::subtasks(task, tasks),
+            tasks,
+            depth + 1,
+        )?);
+    }
+    Ok(buffer)
+}
+
+fn item(config: &Config, task: &Task, depth: usize) -> Result<String, Error> {
+    let indent = " ".repeat(depth * INDENT);
+    let checkbox = if task.checked { "[x]" } else { "[ ]" };
+    let mut line = format!("{indent}- {checkbox} {}", task.content);
+
+    if let Some(due) = due(config, task)? {
+        line.push_str(&format!(" (due {due})"));
+    }
+    for label in &task.labels {
+        line.push_str(&format!(" `@{label}`"));
+    }
+    line.push('\n');
+
+    // The description is a block of its own under the checkbox, indented so that it stays part of the list item.
+    // Without the blank line before it, it would run on from the task content as one paragraph.
+    let description = task.description.trim();
+    if !description.is_empty() {
+        let description_indent = " ".repeat(depth * INDENT + INDENT);
+        line.push('\n');
+        for description_line in description.lines() {
+            if description_line.trim().is_empty() {
+                line.push('\n');
+            } else {
+                line.push_str(&format!("{description_indent}{description_line}\n"));
+            }
+        }
+    }
+
+    Ok(line)
+}
+
+fn due(config: &Config, task: &Task) -> Result<Option<String>, Error> {
+    match task.datetimeinfo(config)? {
+        DateTimeInfo::NoDateTime => Ok(None),
+        DateTimeInfo::Date { date, .. } => Ok(Some(date.format(FORMAT_DATE).to_string())),
+        DateTimeInfo::DateTime { datetime, .. } => {
+            let tz = time::timezone_from_str(&config.get_timezone()?)?;
+            Ok(Some(
+                datetime
+                    .with_timezone(&tz)
+                    .format(FORMAT_DATE_AND_TIME)
+                    .to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::DateInfo;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn section(id: &str, name: &str, section_order: u32) -> Section {
+        Section {
+            id: id.into(),
+            name: name.into(),
+            section_order,
+            ..test::fixtures::section()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_document() {
+        let config = test::fixtures::config().await;
+        let project = test::fixtures::project();
+        let base = Task {
+            project_id: project.id.clone(),
+            section_id: None,
+            parent_id: None,
+            due: None,
+            labels: Vec::new(),
+            description: String::new(),
+            ..test::fixtures::today_task().await
+        };
+
+        let tasks = vec![
+            Task {
+                id: "1".into(),
+                content: "Loose task".into(),
+                ..base.clone()
+            },
+            Task {
+                id: "2".into(),
+                content: "Bake".into(),
+                section_id: Some("b".into()),
+                child_order: 2,
+                labels: vec!["kitchen".into()],
+                description: "Preheat oven\n\nUse the big tray".into(),
+                due: Some(DateInfo {
+                    date: "2025-05-12".into(),
+                    is_recurring: false,
+                    string: "May 12".into(),
+                    lang: "en".into(),
+                    timezone: None,
+                }),
+                ..base.clone()
+            },
+            Task {
+                id: "3".into(),
+                content: "Buy flour".into(),
+                section_id: Some("b".into()),
+                parent_id: Some("2".into()),
+                ..base.clone()
+            },
+            Task {
+                id: "4".into(),
+                content: "Sift flour".into(),
+                section_id: Some("b".into()),
+                parent_id: Some("3".into()),
+                checked: true,
+                ..base.clone()
+            },
+            Task {
+                id: "5".into(),
+                content: "Knead".into(),
+                section_id: Some("b".into()),
+                child_order: 1,
+                ..base.clone()
+            },
+        ];
+        let sections = vec![section("b", "Bread", 2), section("a", "Admin", 1)];
+
+        let markdown = document(&config, &project, &sections, &tasks).unwrap();
+
+        assert_eq!(
+            markdown,
+            "# myproject\n\
+            \n\
+            Something\n\
+            \n\
+            - [ ] Loose task\n\
+            \n\
+            ## Admin\n\
+            \n\
+            ## Bread\n\
+            \n\
+            - [ ] Knead\n\
+            - [ ] Bake (due 2025-05-12) `@kitchen`\n\
+            \n  \
+              Preheat oven\n\
+            \n  \
+              Use the big tray\n  \
+              - [ ] Buy flour\n    \
+                - [x] Sift flour\n"
+        );
+    }
+}
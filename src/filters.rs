@@ -5,8 +5,9 @@ use crate::{
     config::Config,
     errors::Error,
     input::{self},
+    output,
     projects::TaskFilter,
-    tasks::{self, FormatType, Task},
+    tasks::{self, FormatType, NextTask, Task},
     todoist,
 };
 
@@ -47,8 +48,15 @@ pub async fn edit_task(config: &Config, filter: String) -> Result<String, Error>
 pub async fn next_task(config: &Config, filter: &str) -> Result<String, Error> {
     match fetch_next_task(config, filter).await {
         Ok(Some((task, remaining))) => {
-            let comments = todoist::all_comments(config, &task, None).await?;
             config.set_next_task(task.clone()).save().await?;
+            let next = NextTask {
+                task: &task,
+                remaining,
+            };
+            if let Some(result) = output::maybe_single(config, &next) {
+                return result;
+            }
+            let comments = todoist::all_comments(config, &task, None).await?;
             let task_string = task.fmt(comments, config, FormatType::Single, true).await?;
             Ok(format!("{task_string}\n{remaining} task(s) remaining"))
         }
@@ -24,6 +24,7 @@ pub const PRIORITY: &str = "Select priority";
 pub const OPTION: &str = "Select an option";
 pub const SELECT_DATE: &str = "Select a date";
 pub const TASK: &str = "Select a task";
+pub const COMMENT_SELECT: &str = "Select a comment";
 
 // Options
 pub const NAT_LANG: &str = "Natural Language";
@@ -36,6 +37,18 @@ pub const DELETE: &str = "Delete";
 pub const CANCEL: &str = "Cancel";
 pub const QUIT: &str = "Quit";
 pub const SCHEDULE: &str = "Schedule";
+pub const PRIORITIZE: &str = "Prioritize";
+pub const LABEL: &str = "Label";
+pub const DEADLINE: &str = "Deadline";
+pub const MOVE: &str = "Move";
+pub const EDIT: &str = "Edit content";
+pub const SNOOZE_TOMORROW: &str = "Snooze until tomorrow";
+pub const SNOOZE_WEEKDAY: &str = "Snooze until next weekday";
+pub const SNOOZE_WEEK: &str = "Snooze until next week";
+pub const SNOOZE_HOURS: &str = "Snooze for a number of hours";
+pub const POSTPONE: &str = "Postpone this occurrence";
+pub const SKIP_OCCURRENCE: &str = "Skip to next occurrence";
+pub const RESCHEDULE: &str = "Change recurring schedule";
 
 pub enum DateTimeInput {
     Skip,
@@ -44,6 +57,14 @@ pub enum DateTimeInput {
     Text(String),
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Snooze {
+    Hours(i64),
+    Tomorrow,
+    Weekday,
+    Week,
+}
+
 /// Get datetime input from user
 /// skip_or_delete enables the skip and delete options
 /// it is generally true when processing tasks
@@ -115,6 +136,22 @@ pub fn datetime(
     }
 }
 
+/// Get a number of hours to snooze for, i.e. 2 or +2h
+pub fn snooze_hours(mock_string: Option<String>) -> Result<Snooze, Error> {
+    let entry = string("Snooze for how many hours, i.e. 2", mock_string)?;
+    parse_snooze_hours(entry.trim())
+}
+
+fn parse_snooze_hours(entry: &str) -> Result<Snooze, Error> {
+    match entry.trim_start_matches('+').trim_end_matches('h').parse() {
+        Ok(number) if number > 0 => Ok(Snooze::Hours(number)),
+        _ => Err(Error::new(
+            "Snooze Input",
+            &format!("Unrecognized number of hours '{entry}'"),
+        )),
+    }
+}
+
 pub fn date() -> Result<String, Error> {
     let string = DateSelect::new("Select Date")
         .with_help_message(
@@ -214,6 +251,14 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_parse_snooze_hours() {
+        assert_eq!(parse_snooze_hours("2"), Ok(Snooze::Hours(2)));
+        assert_eq!(parse_snooze_hours("+5h"), Ok(Snooze::Hours(5)));
+        assert!(parse_snooze_hours("0").is_err());
+        assert!(parse_snooze_hours("tomorrow").is_err());
+    }
+
     #[test]
     fn can_select() {
         let result = select("type", vec!["there", "are", "words"], Some(0));
@@ -0,0 +1,353 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color, config::Config, errors::Error, store::Store, tasks::Task, time, todoist, trash,
+};
+
+/// Only the most recent changes are kept, i.e. $XDG_CONFIG_HOME/tod.journal
+pub static JOURNAL: Store = Store::new("journal", 200);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Action {
+    Complete,
+    Delete,
+    Update,
+    Move,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Complete => write!(f, "complete"),
+            Action::Delete => write!(f, "delete"),
+            Action::Update => write!(f, "update"),
+            Action::Move => write!(f, "move"),
+        }
+    }
+}
+
+/// A change made to a task, along with the task as it was before the change
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Entry {
+    pub action: Action,
+    pub task: Task,
+    pub recorded_at: String,
+}
+
+/// Remember a task as it was before a change, so that the change can be undone
+pub async fn record(config: &Config, action: Action, task: &Task) -> Result<(), Error> {
+    let recorded_at = time::datetime_now(config)?.to_rfc3339();
+    let entry = Entry {
+        action,
+        task: task.clone(),
+        recorded_at,
+    };
+    JOURNAL.push(config, entry).await
+}
+
+/// Forget the deletion of a task that has been restored from the trash, so that undo does not restore it again,
+/// and point older changes at the new IDs of the task and its subtasks
+pub async fn restored(config: &Config, id: &str, ids: &[(String, String)]) -> Result<(), Error> {
+    let _lock = JOURNAL.lock().await;
+    let mut entries: Vec<Entry> = JOURNAL.load(config).await?;
+    entries.retain(|entry| !(entry.action == Action::Delete && entry.task.id == id));
+    for (old_id, new_id) in ids {
+        replace_id(&mut entries, old_id, new_id);
+    }
+    JOURNAL.save(config, &entries).await
+}
+
+/// Revert the most recent changes, newest first
+pub async fn undo(config: &Config, last: usize) -> Result<String, Error> {
+    let _lock = JOURNAL.lock().await;
+    let mut entries: Vec<Entry> = JOURNAL.load(config).await?;
+    if entries.is_empty() {
+        return Ok(color::green_string("Nothing to undo"));
+    }
+
+    let mut undone = Vec::new();
+    for _ in 0..last {
+        let Some(entry) = entries.pop() else {
+            break;
+        };
+        match revert(config, &entry).await {
+            // The copy of a deleted task has a new ID, so older changes to the task now apply to the copy
+            Ok(ids) => {
+                for (old_id, new_id) in &ids {
+                    replace_id(&mut entries, old_id, new_id);
+                }
+            }
+            Err(e) => {
+                // Keep the change that could not be undone so that it can be tried again
+                entries.push(entry);
+                JOURNAL.save(config, &entries).await?;
+                return Err(e);
+            }
+        }
+        undone.push(format!("- {} '{}'", entry.action, entry.task.content));
+        JOURNAL.save(config, &entries).await?;
+    }
+
+    Ok(format!(
+        "{}\n{}",
+        color::green_string(&format!("Undid {} change(s)", undone.len())),
+        undone.join("\n")
+    ))
+}
+
+/// Returns the old and new IDs of tasks that had to be created again
+async fn revert(config: &Config, entry: &Entry) -> Result<Vec<(String, String)>, Error> {
+    let task = &entry.task;
+    match entry.action {
+        // Completing a recurring task moves it to the next occurrence instead of closing it
+        Action::Complete if task.is_recurring() => todoist::restore_task(config, task)
+            .await
+            .map(|_| Vec::new()),
+        Action::Complete => todoist::reopen_task(config, task, true)
+            .await
+            .map(|_| Vec::new()),
+        // Deleted tasks cannot be brought back, so copies are made from the trash instead
+        Action::Delete => trash::restore_deleted(config, &task.id)
+            .await
+            .map(|restored| restored.ids),
+        Action::Update => todoist::restore_task(config, task)
+            .await
+            .map(|_| Vec::new()),
+        Action::Move => todoist::move_task_back(config, task)
+            .await
+            .map(|_| Vec::new()),
+    }
+}
+
+/// Point the changes to a task, and to its subtasks, at the task's new ID
+fn replace_id(entries: &mut [Entry], old_id: &str, new_id: &str) {
+    for entry in entries {
+        if entry.task.id == old_id {
+            entry.task.id = new_id.to_string();
+        }
+        if entry.task.parent_id.as_deref() == Some(old_id) {
+            entry.task.parent_id = Some(new_id.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{self, responses::ResponseFromFile};
+    use mockito::Matcher;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_record_and_undo() {
+        let mut server = mockito::Server::new_async().await;
+        let get_mock = server
+            .mock("GET", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+        let update_mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .match_body(Matcher::Json(json!({"content": "Old content"})))
+            .with_status(200)
+            .create_async()
+            .await;
+        let comments_mock = server
+            .mock(
+                "GET",
+                "/api/v1/comments/?task_id=6Xqhv4cwxgjwG9w8&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": [], "next_cursor": null}).to_string())
+            .create_async()
+            .await;
+        let subtasks_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=6Xqhv4cwxgjwG9w8&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": [], "next_cursor": null}).to_string())
+            .create_async()
+            .await;
+        let delete_mock = server
+            .mock("DELETE", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .with_status(204)
+            .create_async()
+            .await;
+        let recreate_mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .match_body(Matcher::PartialJson(json!({
+                "content": "TEST",
+                "project_id": "6VRRxv8CM6GVmmgf",
+                "due_datetime": "2025-05-10T12:00:00Z",
+                "duration": 15,
+                "duration_unit": "minute",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+        let updated = Task {
+            content: "Old content".into(),
+            ..task.clone()
+        };
+        record(&config, Action::Update, &updated).await.unwrap();
+        todoist::delete_task(&config, &task, false).await.unwrap();
+        comments_mock.assert();
+        subtasks_mock.assert();
+        delete_mock.assert();
+
+        let entries: Vec<Entry> = JOURNAL.load(&config).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].action, Action::Delete);
+        assert_eq!(entries[1].task, task);
+
+        let result = undo(&config, 5).await.unwrap();
+        assert_eq!(
+            result,
+            format!(
+                "{}\n- delete 'TEST'\n- update 'Old content'",
+                color::green_string("Undid 2 change(s)")
+            )
+        );
+        get_mock.assert();
+        update_mock.assert();
+        recreate_mock.assert();
+        // The deleted task came out of the trash
+        assert_eq!(
+            trash::list(&config).await,
+            Ok(color::green_string("Trash is empty"))
+        );
+
+        assert_eq!(
+            undo(&config, 1).await,
+            Ok(color::green_string("Nothing to undo"))
+        );
+        JOURNAL.remove(&config).await;
+        trash::TRASH.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_undo_after_recreate_uses_new_id() {
+        let mut server = mockito::Server::new_async().await;
+        let comments_mock = server
+            .mock(
+                "GET",
+                "/api/v1/comments/?task_id=6Xqhv4cwxgjwG9w8&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": [], "next_cursor": null}).to_string())
+            .create_async()
+            .await;
+        let subtasks_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=6Xqhv4cwxgjwG9w8&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": [], "next_cursor": null}).to_string())
+            .create_async()
+            .await;
+        let delete_mock = server
+            .mock("DELETE", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .with_status(204)
+            .create_async()
+            .await;
+        // Todoist gives the copy a new ID
+        let recreate_mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Task.read().await)
+            .create_async()
+            .await;
+        let get_mock = server
+            .mock("GET", "/api/v1/tasks/999999")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+        let update_mock = server
+            .mock("POST", "/api/v1/tasks/999999")
+            .match_body(Matcher::Json(json!({"content": "Old content"})))
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+        let updated = Task {
+            content: "Old content".into(),
+            ..task.clone()
+        };
+        record(&config, Action::Update, &updated).await.unwrap();
+        todoist::delete_task(&config, &task, false).await.unwrap();
+
+        undo(&config, 1).await.unwrap();
+        let entries: Vec<Entry> = JOURNAL.load(&config).await.unwrap();
+        assert_eq!(entries[0].task.id, "999999");
+
+        undo(&config, 1).await.unwrap();
+        comments_mock.assert();
+        subtasks_mock.assert();
+        delete_mock.assert();
+        recreate_mock.assert();
+        get_mock.assert();
+        update_mock.assert();
+        JOURNAL.remove(&config).await;
+        trash::TRASH.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_recorded() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+        todoist::update_task_content(&config, &task, "New content".into(), false)
+            .await
+            .unwrap();
+        mock.assert();
+
+        let entries: Vec<Entry> = JOURNAL.load(&config).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, Action::Update);
+        assert_eq!(entries[0].task, task);
+        JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_undo_keeps_failed_change() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8/reopen")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+        record(&config, Action::Complete, &task).await.unwrap();
+
+        assert!(undo(&config, 1).await.is_err());
+        mock.assert();
+        assert_eq!(JOURNAL.load::<Entry>(&config).await.unwrap().len(), 1);
+        JOURNAL.remove(&config).await;
+    }
+}
@@ -1,17 +1,51 @@
+pub mod agenda;
+pub mod week;
+
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::Display;
+use std::path::Path;
 
 use crate::{
     color,
     comments::Comment,
     config::Config,
     errors::Error,
+    export::{self, ExportColumn, ExportFormat},
+    output,
     projects::Project,
-    tasks::{self, FormatType, SortOrder, Task, priority::Priority},
-    todoist,
+    sections::{self, Section},
+    tasks::{self, DateTimeInfo, FormatType, SortOrder, Task, priority::Priority},
+    time, todoist,
 };
+use chrono::NaiveDate;
 use futures::future;
 use tokio::{fs, io::AsyncReadExt, task::JoinError};
 
+const COLLAPSED: &str = "▸ ";
+const EXPANDED: &str = "▾ ";
+/// Each level of subtasks is indented by this many spaces in tree view
+const TREE_INDENT: usize = 2;
+const NO_SECTION: &str = "No section";
+const NO_LABEL: &str = "No label";
+const NO_DATE: &str = "No date";
+const NO_PROJECT: &str = "No project";
+/// i.e. Sat 2025-05-10
+const GROUP_DATE_FORMAT: &str = "%a %Y-%m-%d";
+
+#[derive(clap::ValueEnum, Debug, Copy, Clone)]
+pub enum GroupBy {
+    /// Group by section, in the same order as Todoist
+    Section,
+    /// Group by label, tasks with several labels show up in each group
+    Label,
+    /// Group by priority, highest first
+    Priority,
+    /// Group by the day tasks are due
+    Date,
+    /// Group by project name
+    Project,
+}
+
 #[derive(Clone)]
 pub enum Flag {
     Project(Project),
@@ -28,7 +62,13 @@ impl Display for Flag {
 }
 
 /// Get a list of all tasks
-pub async fn view(config: &mut Config, flag: Flag, sort: &SortOrder) -> Result<String, Error> {
+pub async fn view(
+    config: &mut Config,
+    flag: Flag,
+    sort: &SortOrder,
+    tree: bool,
+    group_by: Option<&GroupBy>,
+) -> Result<String, Error> {
     let list_of_tasks = match flag.clone() {
         Flag::Project(project) => vec![(
             project.name.clone(),
@@ -37,6 +77,19 @@ pub async fn view(config: &mut Config, flag: Flag, sort: &SortOrder) -> Result<S
         Flag::Filter(filter) => todoist::all_tasks_by_filters(config, &filter).await?,
     };
 
+    let list_of_tasks: Vec<(String, Vec<Task>)> = list_of_tasks
+        .into_iter()
+        .map(|(query, tasks)| (query, tasks::sort(tasks, config, sort)))
+        .collect();
+
+    let all_tasks = list_of_tasks
+        .iter()
+        .flat_map(|(_, tasks)| tasks.to_owned())
+        .collect::<Vec<Task>>();
+    if let Some(result) = output::maybe_list(config, &all_tasks) {
+        return result;
+    }
+
     let mut buffer = String::new();
 
     for (query, tasks) in list_of_tasks {
@@ -44,16 +97,331 @@ pub async fn view(config: &mut Config, flag: Flag, sort: &SortOrder) -> Result<S
         buffer.push('\n');
         buffer.push_str(&color::green_string(&title));
         buffer.push('\n');
-        for task in tasks::sort(tasks, config, sort) {
-            let comments = Vec::new();
-            let text = task.fmt(comments, config, FormatType::List, true).await?;
-            buffer.push('\n');
-            buffer.push_str(&text);
+        match group_by {
+            None => buffer.push_str(&render(config, &tasks, tree).await?),
+            Some(group_by) => {
+                for (heading, tasks) in group(config, tasks, group_by).await? {
+                    buffer.push('\n');
+                    buffer.push_str(&color::cyan_string(&heading));
+                    buffer.push('\n');
+                    buffer.push_str(&render(config, &tasks, tree).await?);
+                }
+            }
+        }
+    }
+    Ok(buffer)
+}
+
+async fn render(config: &Config, tasks: &[Task], tree: bool) -> Result<String, Error> {
+    if tree {
+        return render_tree(config, tasks).await;
+    }
+
+    let mut buffer = String::new();
+    for task in tasks {
+        let comments = Vec::new();
+        let text = task.fmt(comments, config, FormatType::List, true).await?;
+        buffer.push('\n');
+        buffer.push_str(&text);
+    }
+    Ok(buffer)
+}
+
+/// Split tasks into headed groups, each group keeps the order of the tasks passed in.
+/// A task with several labels shows up under each of them.
+async fn group(
+    config: &Config,
+    tasks: Vec<Task>,
+    group_by: &GroupBy,
+) -> Result<Vec<(String, Vec<Task>)>, Error> {
+    let sections = match group_by {
+        GroupBy::Section => sections::sections_for_tasks(config, &tasks).await?,
+        _ => Vec::new(),
+    };
+    let projects = config.projects().await?;
+    // Section names are only unique within a project, so name the project as well when there are several
+    let several_projects = tasks
+        .iter()
+        .map(|task| &task.project_id)
+        .collect::<HashSet<&String>>()
+        .len()
+        > 1;
+
+    // Keyed by a sort key so that groups come out in a sensible order
+    let mut groups: BTreeMap<String, (String, Vec<Task>)> = BTreeMap::new();
+    for task in &tasks {
+        let keys = group_keys(
+            config,
+            task,
+            group_by,
+            &sections,
+            &projects,
+            several_projects,
+        )?;
+        for (key, heading) in keys {
+            groups
+                .entry(key)
+                .or_insert_with(|| (heading, Vec::new()))
+                .1
+                .push(task.clone());
+        }
+    }
+
+    Ok(groups.into_values().collect())
+}
+
+/// Returns (sort key, heading) for each group the task belongs in
+fn group_keys(
+    config: &Config,
+    task: &Task,
+    group_by: &GroupBy,
+    sections: &[Section],
+    projects: &[Project],
+    several_projects: bool,
+) -> Result<Vec<(String, String)>, Error> {
+    let keys = match group_by {
+        GroupBy::Section => vec![section_key(task, sections, projects, several_projects)],
+        GroupBy::Label if task.labels.is_empty() => vec![("1".into(), NO_LABEL.into())],
+        GroupBy::Label => task
+            .labels
+            .iter()
+            .map(|label| (format!("0{label}"), format!("@{label}")))
+            .collect(),
+        GroupBy::Priority => vec![(
+            // Highest priority first
+            format!("{}", 5 - task.priority.to_integer()),
+            task.priority.to_string(),
+        )],
+        GroupBy::Date => match task.datetimeinfo(config)? {
+            DateTimeInfo::NoDateTime => vec![("1".into(), NO_DATE.into())],
+            DateTimeInfo::Date { date, .. } => vec![date_key(date)],
+            DateTimeInfo::DateTime { datetime, .. } => {
+                let tz = time::timezone_from_str(&config.get_timezone()?)?;
+                vec![date_key(datetime.with_timezone(&tz).date_naive())]
+            }
+        },
+        GroupBy::Project => match projects
+            .iter()
+            .find(|project| project.id == task.project_id)
+        {
+            None => vec![("1".into(), NO_PROJECT.into())],
+            Some(project) => vec![(
+                format!("0{}{}", project.name, project.id),
+                project.name.clone(),
+            )],
+        },
+    };
+    Ok(keys)
+}
+
+/// Sections are grouped within their project, so that sections with the same name in different projects stay apart
+fn section_key(
+    task: &Task,
+    sections: &[Section],
+    projects: &[Project],
+    several_projects: bool,
+) -> (String, String) {
+    let project_name = projects
+        .iter()
+        .find(|project| project.id == task.project_id)
+        .map_or(NO_PROJECT, |project| project.name.as_str());
+    let project_key = format!("{project_name}\0{}\0", task.project_id);
+    let (key, name) = match sections
+        .iter()
+        .find(|section| Some(&section.id) == task.section_id.as_ref())
+    {
+        None => (format!("{project_key}0"), NO_SECTION),
+        Some(section) => (
+            format!("{project_key}1{:010}{}", section.section_order, section.id),
+            section.name.as_str(),
+        ),
+    };
+    if several_projects {
+        (key, format!("{project_name} / {name}"))
+    } else {
+        (key, name.to_string())
+    }
+}
+
+fn date_key(date: NaiveDate) -> (String, String) {
+    (
+        format!("0{}", date.format(time::FORMAT_DATE)),
+        date.format(GROUP_DATE_FORMAT).to_string(),
+    )
+}
+
+/// Nest subtasks under their parents. Top level tasks keep the chosen sort order and subtasks of collapsed tasks are hidden.
+async fn render_tree(config: &Config, tasks: &[Task]) -> Result<String, Error> {
+    let mut buffer = String::new();
+    let mut stack = tasks::top_level_tasks(tasks)
+        .into_iter()
+        .rev()
+        .map(|task| (task, 0))
+        .collect::<Vec<(&Task, usize)>>();
+
+    while let Some((task, depth)) = stack.pop() {
+        let subtasks = tasks::subtasks(task, tasks);
+        let marker = match (subtasks.is_empty(), task.is_collapsed) {
+            (true, _) => "- ",
+            (false, true) => COLLAPSED,
+            (false, false) => EXPANDED,
+        };
+        let text = task.fmt(Vec::new(), config, FormatType::List, true).await?;
+        let text = format!("{marker}{}", text.strip_prefix("- ").unwrap_or(&text));
+        let indent = " ".repeat(depth * TREE_INDENT);
+        let text = text
+            .split('\n')
+            .map(|line| {
+                if line.is_empty() {
+                    line.to_string()
+                } else {
+                    format!("{indent}{line}")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        buffer.push('\n');
+        buffer.push_str(&text);
+
+        if !task.is_collapsed {
+            stack.extend(subtasks.into_iter().rev().map(|task| (task, depth + 1)));
+        }
+    }
+    Ok(buffer)
+}
+
+/// Export tasks as CSV or TSV instead of formatting them for the terminal
+pub async fn export(
+    config: &Config,
+    flag: Flag,
+    sort: &SortOrder,
+    format: &ExportFormat,
+    columns: &[ExportColumn],
+) -> Result<String, Error> {
+    let list_of_tasks = match flag.clone() {
+        Flag::Project(project) => vec![(
+            project.name.clone(),
+            todoist::all_tasks_by_project(config, &project, None).await?,
+        )],
+        Flag::Filter(filter) => todoist::all_tasks_by_filters(config, &filter).await?,
+    };
+
+    let tasks = list_of_tasks
+        .into_iter()
+        .flat_map(|(_, tasks)| tasks::sort(tasks, config, sort))
+        .collect::<Vec<Task>>();
+
+    export::table(config, &tasks, format, columns).await
+}
+
+/// All tasks for a project, or for all filters combined
+async fn fetch_tasks(config: &Config, flag: &Flag) -> Result<Vec<Task>, Error> {
+    match flag {
+        Flag::Project(project) => todoist::all_tasks_by_project(config, project, None).await,
+        Flag::Filter(filter) => Ok(todoist::all_tasks_by_filters(config, filter)
+            .await?
+            .into_iter()
+            .flat_map(|(_, tasks)| tasks)
+            .collect::<Vec<Task>>()),
+    }
+}
+
+/// Show the tasks completed between two dates, grouped by the day they were completed
+pub async fn completed(
+    config: &Config,
+    flag: Option<Flag>,
+    since: NaiveDate,
+    until: NaiveDate,
+) -> Result<String, Error> {
+    if since > until {
+        return Err(Error::new(
+            "completed",
+            "The start date must be on or before the end date",
+        ));
+    }
+    let (project, filter) = match &flag {
+        None => (None, None),
+        Some(Flag::Project(project)) => (Some(project), None),
+        Some(Flag::Filter(filter)) => (None, Some(filter.as_str())),
+    };
+    let tasks = todoist::all_completed_tasks(config, since, until, project, filter).await?;
+
+    if let Some(result) = output::maybe_list(config, &tasks) {
+        return result;
+    }
+
+    let tz = time::timezone_from_str(&config.get_timezone()?)?;
+    let mut completed = tasks
+        .iter()
+        .filter_map(|task| {
+            let completed_at = task.completed_at.as_ref()?;
+            let datetime = time::datetime_from_str(completed_at, tz).ok()?;
+            Some((datetime.with_timezone(&tz), task))
+        })
+        .collect::<Vec<_>>();
+    completed.sort_by_key(|(datetime, _)| *datetime);
+
+    let range = format!(
+        "{} to {}",
+        since.format(time::FORMAT_DATE),
+        until.format(time::FORMAT_DATE)
+    );
+    if completed.is_empty() {
+        return Ok(color::green_string(&format!(
+            "No tasks completed from {range}"
+        )));
+    }
+
+    let projects = config.projects().await?;
+    let title = format!("{} task(s) completed from {range}", completed.len());
+    let mut buffer = format!("\n{}\n", color::green_string(&title));
+    let mut current_date = None;
+    for (datetime, task) in completed {
+        let date = datetime.date_naive();
+        if current_date != Some(date) {
+            let heading = date.format(GROUP_DATE_FORMAT).to_string();
+            buffer.push_str(&format!("\n{}\n", color::cyan_string(&heading)));
+            current_date = Some(date);
         }
+        let project = match projects.iter().find(|p| p.id == task.project_id) {
+            Some(project) => format!(" {} {}", color::purple_string("#"), project.name),
+            None => String::new(),
+        };
+        buffer.push_str(&format!(
+            "- {}{project}\n",
+            tasks::format::content(task, config)
+        ));
     }
     Ok(buffer)
 }
 
+/// Show the tasks due on a date on a timeline
+pub async fn agenda(config: &Config, flag: Flag, date: NaiveDate) -> Result<String, Error> {
+    let tasks = fetch_tasks(config, &flag).await?;
+    agenda::render(config, &tasks, date)
+}
+
+/// Show the next 7 days as a grid with a column per day
+pub async fn week(config: &Config, flag: Flag) -> Result<String, Error> {
+    let tasks = fetch_tasks(config, &flag).await?;
+    let today = time::naive_date_today(config)?;
+    week::render(config, &tasks, today, week::terminal_width())
+}
+
+/// Write tasks with due dates to an iCalendar file so that they show up in calendar apps
+pub async fn export_ics(config: &Config, flag: Flag, file_path: &Path) -> Result<String, Error> {
+    let tasks = fetch_tasks(config, &flag).await?;
+
+    let (calendar, count) = export::ics::calendar(config, &tasks)?;
+    fs::write(file_path, calendar).await?;
+
+    Ok(color::green_string(&format!(
+        "Wrote {count} event(s) for {flag} to {}",
+        file_path.display()
+    )))
+}
+
 /// Prioritize all unprioritized tasks
 pub async fn prioritize(config: &Config, flag: Flag, sort: &SortOrder) -> Result<String, Error> {
     let tasks = match flag.clone() {
@@ -199,6 +567,44 @@ pub async fn process(config: &Config, flag: Flag, sort: &SortOrder) -> Result<St
     Ok(color::green_string(&success))
 }
 
+/// Go through tasks one at a time with every action available, instead of a separate pass for each
+pub async fn triage(config: &Config, flag: Flag, sort: &SortOrder) -> Result<String, Error> {
+    let tasks = match flag.clone() {
+        Flag::Project(project) => todoist::all_tasks_by_project(config, &project, None).await?,
+        Flag::Filter(filter) => todoist::all_tasks_by_filters(config, &filter)
+            .await?
+            .into_iter()
+            .flat_map(|(_, tasks)| tasks.to_owned())
+            .collect::<Vec<Task>>(),
+    };
+
+    let with_project = match flag {
+        Flag::Project(..) => false,
+        Flag::Filter(..) => true,
+    };
+    let empty_text = format!("No tasks for {flag}");
+    let success = format!("Successfully triaged {flag}");
+
+    if tasks.is_empty() {
+        return Ok(color::green_string(&empty_text));
+    }
+
+    let tasks = tasks::sort(tasks, config, sort);
+    let mut task_count = tasks.len() as i32;
+    let mut handles = Vec::new();
+    for task in tasks {
+        println!();
+        let keep_going =
+            tasks::triage_task(config, task, &mut task_count, with_project, &mut handles).await?;
+        if !keep_going {
+            future::join_all(handles).await;
+            return Ok(color::green_string("Exited"));
+        }
+    }
+    future::join_all(handles).await;
+    Ok(color::green_string(&success))
+}
+
 async fn fetch_comments_for_tasks(
     tasks: Vec<Task>,
     config: &Config,
@@ -333,7 +739,40 @@ mod tests {
         assert_eq!(result, Ok(String::from("Successfully prioritized 'today'")));
         mock.assert();
         mock2.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_triage() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/tasks/filter?query=today&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTasks.read().await)
+            .create_async()
+            .await;
+        let mock2 = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8/close")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        // Complete
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(1);
+
+        let filter = String::from("today");
+        let sort = &SortOrder::Value;
+        let result = triage(&config, Flag::Filter(filter), sort).await;
+        assert_eq!(result, Ok(String::from("Successfully triaged 'today'")));
+        mock.assert();
+        mock2.assert();
+        crate::journal::JOURNAL.remove(&config).await;
     }
+
     #[tokio::test]
     async fn test_timebox() {
         let mut server = mockito::Server::new_async().await;
@@ -479,6 +918,7 @@ mod tests {
         mock.assert();
         mock2.assert();
         mock3.assert();
+        crate::journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
@@ -534,6 +974,7 @@ mod tests {
         mock.assert();
         mock2.assert();
         mock3.assert();
+        crate::journal::JOURNAL.remove(&config).await;
     }
     #[tokio::test]
     async fn test_label() {
@@ -576,6 +1017,7 @@ mod tests {
         );
         mock.assert();
         mock2.assert();
+        crate::journal::JOURNAL.remove(&config_with_timezone).await;
     }
 
     #[tokio::test]
@@ -597,9 +1039,15 @@ mod tests {
         let filter = String::from("today");
         let sort = &SortOrder::Value;
 
-        let tasks = view(&mut config_with_timezone, Flag::Filter(filter), sort)
-            .await
-            .unwrap();
+        let tasks = view(
+            &mut config_with_timezone,
+            Flag::Filter(filter),
+            sort,
+            false,
+            None,
+        )
+        .await
+        .unwrap();
 
         assert!(tasks.contains("Tasks for today"));
         mock.assert();
@@ -626,12 +1074,239 @@ mod tests {
         let project = binding.first().unwrap().clone();
         let sort = &SortOrder::Value;
 
-        let tasks = view(&mut config_with_timezone, Flag::Project(project), sort)
-            .await
-            .unwrap();
+        let tasks = view(
+            &mut config_with_timezone,
+            Flag::Project(project),
+            sort,
+            false,
+            None,
+        )
+        .await
+        .unwrap();
 
         assert!(tasks.contains("Tasks for"));
         assert!(tasks.contains("- TEST\n"));
         mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_render_tree() {
+        let config = test::fixtures::config().await;
+        let base = Task {
+            project_id: "123".into(),
+            parent_id: None,
+            due: None,
+            labels: Vec::new(),
+            priority: Priority::None,
+            ..test::fixtures::today_task().await
+        };
+        let tasks = vec![
+            Task {
+                id: "1".into(),
+                content: "Parent".into(),
+                ..base.clone()
+            },
+            Task {
+                id: "2".into(),
+                content: "Second child".into(),
+                parent_id: Some("1".into()),
+                child_order: 2,
+                ..base.clone()
+            },
+            Task {
+                id: "3".into(),
+                content: "First child".into(),
+                parent_id: Some("1".into()),
+                child_order: 1,
+                is_collapsed: true,
+                ..base.clone()
+            },
+            Task {
+                id: "4".into(),
+                content: "Hidden grandchild".into(),
+                parent_id: Some("3".into()),
+                ..base.clone()
+            },
+        ];
+
+        let tree = render_tree(&config, &tasks).await.unwrap();
+        let lines = tree
+            .lines()
+            .filter(|line| {
+                let line = line.trim_start();
+                line.starts_with(COLLAPSED) || line.starts_with(EXPANDED) || line.starts_with("- ")
+            })
+            .collect::<Vec<&str>>();
+
+        assert_eq!(
+            lines,
+            vec!["▾ Parent", "  ▸ First child", "  - Second child"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_group() {
+        let config = test::fixtures::config().await;
+        let base = test::fixtures::today_task().await;
+        let tasks = vec![
+            Task {
+                id: "1".into(),
+                labels: vec!["home".into(), "computer".into()],
+                priority: Priority::Low,
+                due: None,
+                ..base.clone()
+            },
+            Task {
+                id: "2".into(),
+                labels: Vec::new(),
+                priority: Priority::High,
+                project_id: "123".into(),
+                ..base.clone()
+            },
+            Task {
+                id: "3".into(),
+                labels: vec!["home".into()],
+                priority: Priority::High,
+                ..base.clone()
+            },
+        ];
+        let ids = |groups: Vec<(String, Vec<Task>)>| {
+            groups
+                .into_iter()
+                .map(|(heading, tasks)| {
+                    let ids = tasks.into_iter().map(|task| task.id).collect::<Vec<_>>();
+                    (heading, ids.join(","))
+                })
+                .collect::<Vec<(String, String)>>()
+        };
+        let today = time::naive_date_today(&config)
+            .unwrap()
+            .format(GROUP_DATE_FORMAT)
+            .to_string();
+
+        let labels = group(&config, tasks.clone(), &GroupBy::Label).await;
+        assert_eq!(
+            ids(labels.unwrap()),
+            vec![
+                ("@computer".to_string(), "1".to_string()),
+                ("@home".to_string(), "1,3".to_string()),
+                ("No label".to_string(), "2".to_string()),
+            ]
+        );
+
+        let priorities = group(&config, tasks.clone(), &GroupBy::Priority).await;
+        assert_eq!(
+            ids(priorities.unwrap()),
+            vec![
+                ("HIGH (P1)".to_string(), "2,3".to_string()),
+                ("LOW (P3)".to_string(), "1".to_string()),
+            ]
+        );
+
+        let dates = group(&config, tasks.clone(), &GroupBy::Date).await;
+        assert_eq!(
+            ids(dates.unwrap()),
+            vec![
+                (today, "2,3".to_string()),
+                ("No date".to_string(), "1".to_string()),
+            ]
+        );
+
+        let projects = group(&config, tasks, &GroupBy::Project).await;
+        assert_eq!(
+            ids(projects.unwrap()),
+            vec![
+                ("myproject".to_string(), "2".to_string()),
+                ("No project".to_string(), "1,3".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_section_key() {
+        let project = test::fixtures::project();
+        let other = Project {
+            id: "456".into(),
+            name: "other".into(),
+            ..project.clone()
+        };
+        let backlog = Section {
+            id: "1".into(),
+            name: "Backlog".into(),
+            project_id: project.id.clone(),
+            ..test::fixtures::section()
+        };
+        let other_backlog = Section {
+            id: "2".into(),
+            project_id: other.id.clone(),
+            ..backlog.clone()
+        };
+        let sections = [backlog, other_backlog];
+        let projects = [project, other];
+        let task = Task {
+            project_id: "123".into(),
+            section_id: Some("1".into()),
+            ..test::fixtures::today_task().await
+        };
+        let other_task = Task {
+            project_id: "456".into(),
+            section_id: Some("2".into()),
+            ..task.clone()
+        };
+
+        let (key, heading) = section_key(&task, &sections, &projects, true);
+        let (other_key, other_heading) = section_key(&other_task, &sections, &projects, true);
+        assert_ne!(key, other_key);
+        assert_eq!(heading, "myproject / Backlog");
+        assert_eq!(other_heading, "other / Backlog");
+
+        let (_, heading) = section_key(&task, &sections, &projects, false);
+        assert_eq!(heading, "Backlog");
+        let unsectioned = Task {
+            section_id: None,
+            ..task
+        };
+        let (_, heading) = section_key(&unsectioned, &sections, &projects, true);
+        assert_eq!(heading, "myproject / No section");
+    }
+
+    #[tokio::test]
+    async fn test_completed() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock(
+                "GET",
+                "/api/v1/tasks/completed/by_completion_date?since=2025-05-09T07%3A00%3A00Z&until=2025-05-11T07%3A00%3A00Z&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::CompletedTasks.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .with_timezone("America/Vancouver");
+        let since = NaiveDate::from_ymd_opt(2025, 5, 9).unwrap();
+        let until = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+
+        let result = completed(&config, None, since, until).await;
+        assert_eq!(
+            result,
+            Ok("\n3 task(s) completed from 2025-05-09 to 2025-05-10\n\
+                \n\
+                Fri 2025-05-09\n\
+                - Fix bug # myproject\n\
+                \n\
+                Sat 2025-05-10\n\
+                - Buy milk\n\
+                - Write report # myproject\n"
+                .to_string())
+        );
+
+        let result = completed(&config, None, until, since).await;
+        assert!(result.is_err());
+        mock.assert();
+    }
 }
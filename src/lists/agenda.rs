@@ -0,0 +1,259 @@
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate};
+use chrono_tz::Tz;
+
+use crate::{
+    color,
+    config::Config,
+    errors::Error,
+    tasks::{DateTimeInfo, Duration, Task, Unit, format},
+    time,
+};
+
+const FORMAT_TIME: &str = "%H:%M";
+/// i.e. Sat 2025-05-10
+const FORMAT_HEADING_DATE: &str = "%a %Y-%m-%d";
+/// i.e. May 12 2025
+const FORMAT_FILTER_DATE: &str = "%b %-d %Y";
+/// Width of "09:00 - 10:30" so that content lines up for tasks without a duration
+const TIME_RANGE_WIDTH: usize = 13;
+
+/// Todoist filter for the tasks due on a date, used when no project or filter is given
+pub fn filter_for_date(config: &Config, date: NaiveDate) -> Result<String, Error> {
+    if date == time::naive_date_today(config)? {
+        Ok("today".into())
+    } else {
+        Ok(format!("date: {}", date.format(FORMAT_FILTER_DATE)))
+    }
+}
+
+/// A task with a due time on the agenda date
+struct Block<'a> {
+    task: &'a Task,
+    start: DateTime<Tz>,
+    end: Option<DateTime<Tz>>,
+}
+
+/// Lay out the tasks due on a date on a time axis, with the free time between them and any overlaps.
+/// Tasks that are due on the date without a time are listed separately.
+pub fn render(config: &Config, tasks: &[Task], date: NaiveDate) -> Result<String, Error> {
+    let tz = time::timezone_from_str(&config.get_timezone()?)?;
+
+    let mut blocks = Vec::new();
+    let mut untimed = Vec::new();
+    for task in tasks {
+        match task.datetimeinfo(config)? {
+            DateTimeInfo::NoDateTime => (),
+            DateTimeInfo::Date { date: due_date, .. } => {
+                if due_date == date {
+                    untimed.push(task);
+                }
+            }
+            DateTimeInfo::DateTime { datetime, .. } => {
+                let start = datetime.with_timezone(&tz);
+                if start.date_naive() == date {
+                    let end = match task.duration {
+                        None => None,
+                        Some(Duration {
+                            amount,
+                            unit: Unit::Minute,
+                        }) => Some(start + ChronoDuration::minutes(amount.into())),
+                        Some(Duration {
+                            amount,
+                            unit: Unit::Day,
+                        }) => Some(start + ChronoDuration::days(amount.into())),
+                    };
+                    blocks.push(Block { task, start, end });
+                }
+            }
+        }
+    }
+    blocks.sort_by_key(|block| block.start);
+
+    let title = format!("Agenda for {}", date.format(FORMAT_HEADING_DATE));
+    let mut buffer = format!("\n{}\n", color::green_string(&title));
+
+    if blocks.is_empty() && untimed.is_empty() {
+        buffer.push_str("\nNothing scheduled\n");
+        return Ok(buffer);
+    }
+
+    if !blocks.is_empty() {
+        buffer.push('\n');
+    }
+
+    // The latest end time seen so far, used to find gaps and overlaps
+    let mut busy_until: Option<DateTime<Tz>> = None;
+    for block in blocks {
+        if let Some(until) = busy_until {
+            if block.start > until {
+                let minutes = (block.start - until).num_minutes();
+                let range = time_range(&until, Some(&block.start), date);
+                let free = color::green_string(&format!("free for {}", length(minutes)));
+                buffer.push_str(&format!("{range}  {free}\n"));
+            }
+        }
+
+        let overlaps = busy_until.is_some_and(|until| block.start < until);
+        let range = time_range(&block.start, block.end.as_ref(), date);
+        let content = format::content(block.task, config);
+        let overlap = if overlaps {
+            format!(" {}", color::red_string("(overlaps)"))
+        } else {
+            String::new()
+        };
+        buffer.push_str(&format!("{range}  {content}{overlap}\n"));
+
+        let block_end = block.end.unwrap_or(block.start);
+        busy_until = Some(busy_until.map_or(block_end, |until| until.max(block_end)));
+    }
+
+    if !untimed.is_empty() {
+        buffer.push_str(&format!("\n{}\n", color::cyan_string("No time")));
+        for task in untimed {
+            buffer.push_str(&format!("- {}\n", format::content(task, config)));
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Formats "09:00 - 10:30", or only the start time when there is no end. Ends on a later day are marked.
+fn time_range(start: &DateTime<Tz>, end: Option<&DateTime<Tz>>, date: NaiveDate) -> String {
+    let start_string = start.format(FORMAT_TIME).to_string();
+    let range = match end {
+        None => start_string,
+        Some(end) => {
+            let days = (end.date_naive() - date).num_days();
+            let later_day = if days > 0 {
+                format!(" +{days}d")
+            } else {
+                String::new()
+            };
+            format!("{start_string} - {}{later_day}", end.format(FORMAT_TIME))
+        }
+    };
+    format!("{range:<TIME_RANGE_WIDTH$}")
+}
+
+fn length(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes} min"),
+        (hours, 0) => format!("{hours} h"),
+        (hours, minutes) => format!("{hours} h {minutes} min"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::DateInfo;
+    use crate::tasks::priority::Priority;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn due(date: &str) -> Option<DateInfo> {
+        Some(DateInfo {
+            date: date.into(),
+            is_recurring: false,
+            string: String::new(),
+            lang: "en".into(),
+            timezone: None,
+        })
+    }
+
+    fn minutes(amount: u32) -> Option<Duration> {
+        Some(Duration {
+            amount,
+            unit: Unit::Minute,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_filter_for_date() {
+        let config = test::fixtures::config().await;
+        let today = time::naive_date_today(&config).unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 5, 12).unwrap();
+
+        assert_eq!(filter_for_date(&config, today).unwrap(), "today");
+        assert_eq!(filter_for_date(&config, date).unwrap(), "date: May 12 2025");
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(length(45), "45 min");
+        assert_eq!(length(120), "2 h");
+        assert_eq!(length(90), "1 h 30 min");
+    }
+
+    #[tokio::test]
+    async fn test_render() {
+        let config = test::fixtures::config().await.with_timezone("UTC");
+        let base = Task {
+            priority: Priority::None,
+            ..test::fixtures::today_task().await
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 5, 12).unwrap();
+        let tasks = vec![
+            Task {
+                content: "Meeting".into(),
+                due: due("2025-05-12T10:00:00Z"),
+                duration: minutes(60),
+                ..base.clone()
+            },
+            Task {
+                content: "Write report".into(),
+                due: due("2025-05-12T09:00:00Z"),
+                duration: minutes(30),
+                ..base.clone()
+            },
+            Task {
+                content: "Call".into(),
+                due: due("2025-05-12T10:30:00Z"),
+                duration: minutes(45),
+                ..base.clone()
+            },
+            Task {
+                content: "Take pill".into(),
+                due: due("2025-05-12T12:00:00Z"),
+                duration: None,
+                ..base.clone()
+            },
+            Task {
+                content: "Pay rent".into(),
+                due: due("2025-05-12"),
+                ..base.clone()
+            },
+            Task {
+                content: "Tomorrow".into(),
+                due: due("2025-05-13T09:00:00Z"),
+                ..base.clone()
+            },
+        ];
+
+        assert_eq!(
+            render(&config, &tasks, date).unwrap(),
+            "\nAgenda for Mon 2025-05-12\n\
+            \n\
+            09:00 - 09:30  Write report\n\
+            09:30 - 10:00  free for 30 min\n\
+            10:00 - 11:00  Meeting\n\
+            10:30 - 11:15  Call (overlaps)\n\
+            11:15 - 12:00  free for 45 min\n\
+            12:00          Take pill\n\
+            \n\
+            No time\n\
+            - Pay rent\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_empty() {
+        let config = test::fixtures::config().await;
+        let date = NaiveDate::from_ymd_opt(2025, 5, 12).unwrap();
+
+        assert_eq!(
+            render(&config, &[], date).unwrap(),
+            "\nAgenda for Mon 2025-05-12\n\nNothing scheduled\n"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use chrono::{Duration as ChronoDuration, NaiveDate};
+use terminal_size::{Width, terminal_size};
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    color,
+    config::Config,
+    errors::Error,
+    tasks::{DateTimeInfo, Task},
+    time::{self, FORMAT_DATE},
+};
+
+pub const FILTER: &str = "overdue | 7 days";
+const DAYS: usize = 7;
+const SEPARATOR: &str = "│";
+/// Used when the terminal width cannot be determined
+const DEFAULT_WIDTH: usize = 140;
+/// Columns are never narrower than this, the grid overflows instead
+const MIN_COLUMN_WIDTH: usize = 10;
+const FORMAT_TIME: &str = "%H:%M";
+/// i.e. Sat 05-10
+const FORMAT_HEADING_DATE: &str = "%a %m-%d";
+const DEADLINE_ICON: &str = "⚑";
+
+enum Entry<'a> {
+    /// Due on the day, with an optional time
+    Due {
+        task: &'a Task,
+        time: Option<String>,
+    },
+    /// Was due on an earlier day, shown on the first day
+    Overdue(&'a Task),
+    /// The deadline falls on the day
+    Deadline(&'a Task),
+}
+
+pub fn terminal_width() -> usize {
+    match terminal_size() {
+        Some((Width(width), _)) => width.into(),
+        None => DEFAULT_WIDTH,
+    }
+}
+
+/// Render a grid of the 7 days from start, with a column per day. Overdue tasks and passed deadlines are shown on the first day.
+pub fn render(
+    config: &Config,
+    tasks: &[Task],
+    start: NaiveDate,
+    width: usize,
+) -> Result<String, Error> {
+    let days = (0..DAYS)
+        .map(|offset| start + ChronoDuration::days(offset as i64))
+        .collect::<Vec<NaiveDate>>();
+    let columns = bucket(config, tasks, &days)?;
+
+    let column_width = (width.saturating_sub(DAYS - 1) / DAYS).max(MIN_COLUMN_WIDTH);
+
+    let header = days
+        .iter()
+        .map(|day| {
+            color::green_string(&cell(
+                &day.format(FORMAT_HEADING_DATE).to_string(),
+                column_width,
+            ))
+        })
+        .collect::<Vec<String>>()
+        .join(SEPARATOR);
+    let divider = vec!["─".repeat(column_width); DAYS].join("┼");
+
+    let mut lines = vec![header, divider];
+    let rows = columns.iter().map(Vec::len).max().unwrap_or_default();
+    for row in 0..rows {
+        let line = columns
+            .iter()
+            .map(|entries| match entries.get(row) {
+                None => cell("", column_width),
+                Some(entry) => entry_cell(entry, column_width),
+            })
+            .collect::<Vec<String>>()
+            .join(SEPARATOR);
+        lines.push(line);
+    }
+
+    Ok(format!("\n{}\n", lines.join("\n")))
+}
+
+/// Put each task in the column for the day it is due, and again on the day of its deadline
+fn bucket<'a>(
+    config: &Config,
+    tasks: &'a [Task],
+    days: &[NaiveDate],
+) -> Result<Vec<Vec<Entry<'a>>>, Error> {
+    let tz = time::timezone_from_str(&config.get_timezone()?)?;
+    let first_day = days[0];
+    let column = |date: NaiveDate| -> Option<usize> {
+        if date < first_day {
+            Some(0)
+        } else {
+            days.iter().position(|day| *day == date)
+        }
+    };
+
+    let mut timed: Vec<Vec<(String, &Task)>> = vec![Vec::new(); DAYS];
+    let mut untimed: Vec<Vec<Entry>> = (0..DAYS).map(|_| Vec::new()).collect();
+    for task in tasks {
+        match task.datetimeinfo(config)? {
+            DateTimeInfo::NoDateTime => (),
+            DateTimeInfo::Date { date, .. } => match column(date) {
+                Some(index) if date < first_day => untimed[index].push(Entry::Overdue(task)),
+                Some(index) => untimed[index].push(Entry::Due { task, time: None }),
+                None => (),
+            },
+            DateTimeInfo::DateTime { datetime, .. } => {
+                let datetime = datetime.with_timezone(&tz);
+                let date = datetime.date_naive();
+                match column(date) {
+                    Some(index) if date < first_day => untimed[index].push(Entry::Overdue(task)),
+                    Some(index) => {
+                        timed[index].push((datetime.format(FORMAT_TIME).to_string(), task))
+                    }
+                    None => (),
+                }
+            }
+        }
+
+        if let Some(deadline) = &task.deadline {
+            let date = NaiveDate::parse_from_str(&deadline.date, FORMAT_DATE)?;
+            if let Some(index) = column(date) {
+                untimed[index].push(Entry::Deadline(task));
+            }
+        }
+    }
+
+    // Overdue first, then timed tasks in order, then the rest of the day
+    let columns = timed
+        .into_iter()
+        .zip(untimed)
+        .map(|(mut timed, untimed)| {
+            timed.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let (overdue, rest): (Vec<Entry>, Vec<Entry>) = untimed
+                .into_iter()
+                .partition(|entry| matches!(entry, Entry::Overdue(_)));
+            overdue
+                .into_iter()
+                .chain(timed.into_iter().map(|(time, task)| Entry::Due {
+                    task,
+                    time: Some(time),
+                }))
+                .chain(rest)
+                .collect()
+        })
+        .collect();
+
+    Ok(columns)
+}
+
+fn entry_cell(entry: &Entry, width: usize) -> String {
+    match entry {
+        Entry::Due {
+            task,
+            time: Some(time),
+        } => cell(&format!("{time} {}", task.content), width),
+        Entry::Due { task, time: None } => cell(&task.content, width),
+        Entry::Overdue(task) => color::red_string(&cell(&task.content, width)),
+        Entry::Deadline(task) => {
+            color::yellow_string(&cell(&format!("{DEADLINE_ICON} {}", task.content), width))
+        }
+    }
+}
+
+/// Pads or truncates text to exactly the column width, colors must be applied afterwards.
+/// Width is counted in terminal columns, so that wide characters such as CJK keep the grid aligned.
+fn cell(text: &str, width: usize) -> String {
+    let mut cell = String::new();
+    let mut used = 0;
+    for character in text.chars() {
+        let character_width = character.width().unwrap_or_default();
+        if used + character_width > width {
+            break;
+        }
+        cell.push(character);
+        used += character_width;
+    }
+    cell.push_str(&" ".repeat(width - used));
+    cell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::{DateInfo, Deadline};
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    fn due(date: &str) -> Option<DateInfo> {
+        Some(DateInfo {
+            date: date.into(),
+            is_recurring: false,
+            string: String::new(),
+            lang: "en".into(),
+            timezone: None,
+        })
+    }
+
+    #[test]
+    fn test_cell() {
+        assert_eq!(cell("Gym", 5), "Gym  ");
+        assert_eq!(cell("Standup", 5), "Stand");
+        assert_eq!(cell("⚑ Report", 4), "⚑ Re");
+        // Each of these takes two columns, so a column of 5 fits two and a space
+        assert_eq!(cell("日本語のタスク", 5), "日本 ");
+    }
+
+    #[tokio::test]
+    async fn test_render() {
+        let config = test::fixtures::config().await.with_timezone("UTC");
+        let base = Task {
+            deadline: None,
+            ..test::fixtures::today_task().await
+        };
+        let start = NaiveDate::from_ymd_opt(2025, 5, 12).unwrap();
+        let tasks = vec![
+            Task {
+                content: "Gym".into(),
+                due: due("2025-05-12"),
+                ..base.clone()
+            },
+            Task {
+                content: "Standup".into(),
+                due: due("2025-05-12T09:00:00Z"),
+                ..base.clone()
+            },
+            Task {
+                content: "Late".into(),
+                due: due("2025-05-01"),
+                ..base.clone()
+            },
+            Task {
+                content: "Report".into(),
+                due: due("2025-05-13"),
+                deadline: Some(Deadline {
+                    date: "2025-05-18".into(),
+                    lang: "en".into(),
+                }),
+                ..base.clone()
+            },
+            Task {
+                content: "Next week".into(),
+                due: due("2025-05-19"),
+                ..base.clone()
+            },
+        ];
+
+        let grid = render(&config, &tasks, start, 76).unwrap();
+
+        assert_eq!(
+            grid,
+            "\n\
+            Mon 05-12 │Tue 05-13 │Wed 05-14 │Thu 05-15 │Fri 05-16 │Sat 05-17 │Sun 05-18 \n\
+            ──────────┼──────────┼──────────┼──────────┼──────────┼──────────┼──────────\n\
+            Late      │Report    │          │          │          │          │⚑ Report  \n\
+            09:00 Stan│          │          │          │          │          │          \n\
+            Gym       │          │          │          │          │          │          \n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_minimum_width() {
+        let config = test::fixtures::config().await;
+        let start = NaiveDate::from_ymd_opt(2025, 5, 12).unwrap();
+
+        let grid = render(&config, &[], start, 20).unwrap();
+        let header = grid.lines().nth(1).unwrap();
+
+        assert_eq!(header.chars().count(), MIN_COLUMN_WIDTH * DAYS + DAYS - 1);
+    }
+}
@@ -8,17 +8,21 @@ extern crate clap;
 
 use cargo::Version;
 use clap::{Parser, Subcommand};
+use comments::{Attachment, Comment};
 use config::Config;
 use errors::Error;
+use export::{DocumentFormat, ExportColumn, ExportFormat};
 use input::DateTimeInput;
 use inquire::Confirm;
-use lists::Flag;
+use lists::{Flag, GroupBy};
+use output::OutputFormat;
+use projects::Project;
 use shell::Shell;
 use std::fmt::Display;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use tasks::priority::Priority;
-use tasks::{SortOrder, TaskAttribute, priority};
+use tasks::{SortOrder, Task, TaskAttribute, priority};
 use tokio::sync::mpsc::UnboundedSender;
 use walkdir::WalkDir;
 
@@ -31,20 +35,27 @@ mod comments;
 mod config;
 mod debug;
 mod errors;
+mod export;
 mod filters;
 mod id;
 mod input;
+mod journal;
 mod labels;
 mod lists;
 mod oauth;
+mod output;
 mod projects;
 mod sections;
 mod shell;
+mod stats;
+mod store;
 mod tasks;
+mod templates;
 mod test;
 mod test_time;
 mod time;
 mod todoist;
+mod trash;
 mod update;
 mod users;
 // Values pulled from Cargo.toml
@@ -66,6 +77,9 @@ const BUILD_TARGET: &str = env!("BUILD_TARGET");
 const BUILD_PROFILE: &str = env!("BUILD_PROFILE");
 const BUILD_TIMESTAMP: &str = env!("BUILD_TIMESTAMP");
 const NO_PROJECTS_ERR: &str = "No projects in config. Add projects with `tod project import`";
+const ID_OR_SEARCH_ERR: &str = "Use --id or --search";
+/// How far back to look for completed tasks when searching for a task to reopen
+const REOPEN_SEARCH_WEEKS: i64 = 4;
 
 #[derive(Parser, Clone)]
 #[command(name = NAME)]
@@ -86,6 +100,10 @@ struct Cli {
     /// Time to wait for a response from API in seconds. Defaults to 30.
     timeout: Option<u64>,
 
+    #[arg(long, global = true, default_value_t = OutputFormat::Text)]
+    /// Output format for list view, list completed, project list, task next, and task quick-add
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -131,6 +149,33 @@ enum Commands {
     #[clap(alias = "e")]
     /// (e) Commands for manually testing Tod against the API
     Test(TestCommands),
+
+    #[clap(alias = "r")]
+    /// (r) Report completions per day and week, streaks, overdue tasks per project, and the age of open tasks
+    Stats(Stats),
+
+    #[clap(alias = "u")]
+    /// (u) Revert the most recent changes made by Tod, such as completing, deleting, editing or moving a task
+    Undo(Undo),
+
+    #[command(subcommand)]
+    #[clap(alias = "d")]
+    /// (d) Commands for tasks deleted with Tod, which are kept locally so that they can be restored
+    Trash(TrashCommands),
+}
+
+#[derive(Parser, Debug, Clone)]
+struct Stats {
+    #[arg(short, long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=13))]
+    /// Number of calendar weeks to report on, from 1 to 13, including the current one. Todoist allows up to 3 months of history.
+    weeks: u8,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct Undo {
+    #[arg(short, long, default_value_t = 1)]
+    /// Number of changes to revert, newest first
+    last: usize,
 }
 
 // -- PROJECTS --
@@ -164,6 +209,34 @@ enum ProjectCommands {
     #[clap(alias = "e")]
     /// (e) Empty a project by putting tasks in other projects"
     Empty(ProjectEmpty),
+
+    #[clap(alias = "x")]
+    /// (x) Export a project's sections and tasks as a document, i.e. Markdown with nested checklists for subtasks
+    Export(ProjectExport),
+
+    #[command(subcommand)]
+    #[clap(alias = "m")]
+    /// (m) List, add, edit and delete the comments on a project
+    Comments(ProjectCommentsCommands),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ProjectCommentsCommands {
+    #[clap(alias = "l")]
+    /// (l) List all comments on a project in full
+    List(ProjectCommentsList),
+
+    #[clap(alias = "a")]
+    /// (a) Add a comment to a project
+    Add(ProjectCommentsAdd),
+
+    #[clap(alias = "e")]
+    /// (e) Edit a comment on a project
+    Edit(ProjectCommentsEdit),
+
+    #[clap(alias = "d")]
+    /// (d) Delete a comment on a project
+    Delete(ProjectCommentsDelete),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -234,6 +307,69 @@ struct ProjectEmpty {
     project: Option<String>,
 }
 
+#[derive(Parser, Debug, Clone)]
+struct ProjectExport {
+    #[arg(short, long)]
+    /// Project to export
+    project: Option<String>,
+
+    #[arg(long, default_value_t = DocumentFormat::Markdown)]
+    /// Format of the exported document
+    format: DocumentFormat,
+
+    #[arg(short, long)]
+    /// File to write the document to, prints to the terminal when omitted
+    file: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ProjectCommentsList {
+    #[arg(short, long)]
+    /// Project with the comments
+    project: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ProjectCommentsAdd {
+    #[arg(short, long)]
+    /// Project to comment on
+    project: Option<String>,
+
+    #[arg(short, long)]
+    /// Content for comment
+    content: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ProjectCommentsEdit {
+    #[arg(short, long)]
+    /// Project with the comment, used to choose the comment when --comment is not given
+    project: Option<String>,
+
+    #[arg(long)]
+    /// ID of the comment to edit
+    comment: Option<String>,
+
+    #[arg(short, long)]
+    /// New content for the comment
+    content: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ProjectCommentsDelete {
+    #[arg(short, long)]
+    /// Project with the comment, used to choose the comment when --comment is not given
+    project: Option<String>,
+
+    #[arg(long)]
+    /// ID of the comment to delete
+    comment: Option<String>,
+
+    #[arg(long)]
+    /// Skip confirmation
+    force: bool,
+}
+
 // -- SECTIONS --
 
 #[derive(Subcommand, Debug, Clone)]
@@ -267,7 +403,7 @@ enum TaskCommands {
     Create(TaskCreate),
 
     #[clap(alias = "e")]
-    /// (e) Edit an existing task's content
+    /// (e) Edit an existing task, interactively or by ID with flags
     Edit(TaskEdit),
 
     #[clap(alias = "n")]
@@ -275,12 +411,60 @@ enum TaskCommands {
     Next(TaskNext),
 
     #[clap(alias = "o")]
-    /// (o) Complete the last task fetched with the next command
+    /// (o) Complete a task by ID or search, or the last task fetched with the next command
     Complete(TaskComplete),
 
+    #[clap(alias = "d")]
+    /// (d) Delete a task by ID or search
+    Delete(TaskDelete),
+
+    #[clap(alias = "r")]
+    /// (r) Reopen a completed task by ID or search
+    Reopen(TaskReopen),
+
+    #[clap(alias = "s")]
+    /// (s) Show a task with its section, subtasks, attachments and comments
+    Show(TaskShow),
+
+    #[clap(alias = "mv")]
+    /// (mv) Move a task, along with its subtasks, to another project or section
+    Move(TaskMove),
+
     #[clap(alias = "m")]
     /// (m) Add a comment to the last task fetched with the next command
     Comment(TaskComment),
+
+    #[command(subcommand)]
+    #[clap(alias = "cm")]
+    /// (cm) List, edit and delete the comments on a task
+    Comments(TaskCommentsCommands),
+
+    #[clap(alias = "at")]
+    /// (at) List the files and images attached to a task's comments, or download them
+    Attachments(TaskAttachments),
+
+    #[clap(alias = "cl")]
+    /// (cl) Create a copy of a task along with its subtasks
+    Clone(TaskClone),
+
+    #[clap(alias = "tp")]
+    /// (tp) Create a task and its subtasks from a template in the config
+    Template(TaskTemplate),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum TaskCommentsCommands {
+    #[clap(alias = "l")]
+    /// (l) List all comments on a task in full
+    List(TaskCommentsList),
+
+    #[clap(alias = "e")]
+    /// (e) Edit a comment on a task
+    Edit(TaskCommentsEdit),
+
+    #[clap(alias = "d")]
+    /// (d) Delete a comment on a task
+    Delete(TaskCommentsDelete),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -320,17 +504,69 @@ struct TaskCreate {
     #[arg(short, long)]
     /// List of labels to choose from, to be applied to each entry. Use flag once per label
     label: Vec<String>,
+
+    #[arg(short = 'a', long)]
+    /// Create as a subtask of this task, given as an ID or a search term for a task in the project
+    parent: Option<String>,
+
+    #[arg(long)]
+    /// Deadline in format YYYY-MM-DD
+    deadline: Option<String>,
+
+    #[arg(long)]
+    /// Duration in minutes, requires a due time
+    duration: Option<u32>,
 }
 
 #[derive(Parser, Debug, Clone)]
 struct TaskEdit {
     #[arg(short, long)]
-    /// The project containing the task
+    /// The project containing the task. With --id, the project to move the task to instead.
     project: Option<String>,
 
     #[arg(short, long)]
     /// The filter containing the task
     filter: Option<String>,
+
+    #[arg(short, long)]
+    /// ID of the task to edit without prompting, using the flags below
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// New content for the task
+    content: Option<String>,
+
+    #[arg(short, long)]
+    /// New description for the task
+    description: Option<String>,
+
+    #[arg(short = 'r', long, value_parser = clap::value_parser!(u8).range(1..=4))]
+    /// New priority from 1 (without priority) to 4 (highest)
+    priority: Option<u8>,
+
+    #[arg(short = 'u', long)]
+    /// New due date in format YYYY-MM-DD, YYYY-MM-DD HH:MM, or natural language
+    due: Option<String>,
+
+    #[arg(long)]
+    /// New deadline in format YYYY-MM-DD
+    deadline: Option<String>,
+
+    #[arg(long)]
+    /// New duration in minutes
+    duration: Option<u32>,
+
+    #[arg(long)]
+    /// Label to add to the task. Use flag once per label
+    add_label: Vec<String>,
+
+    #[arg(long)]
+    /// Label to remove from the task. Use flag once per label
+    remove_label: Vec<String>,
+
+    #[arg(short, long)]
+    /// Name of the section to move the task to, in its project or the one given with --project
+    section: Option<String>,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -345,15 +581,204 @@ struct TaskNext {
 }
 
 #[derive(Parser, Debug, Clone)]
-struct TaskComplete {}
+struct TaskComplete {
+    #[arg(short, long, conflicts_with_all = ["search", "project", "filter"])]
+    /// ID of the task to complete
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content, prompting unless exactly one task contains the text
+    search: Option<String>,
+
+    #[arg(short, long, requires = "search")]
+    /// Only search tasks in this project
+    project: Option<String>,
+
+    #[arg(short, long, requires = "search")]
+    /// Only search tasks that match this filter
+    filter: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskDelete {
+    #[arg(short, long, required_unless_present = "search", conflicts_with_all = ["search", "project", "filter"])]
+    /// ID of the task to delete
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content, prompting unless exactly one task contains the text
+    search: Option<String>,
+
+    #[arg(short, long, requires = "search")]
+    /// Only search tasks in this project
+    project: Option<String>,
+
+    #[arg(short, long, requires = "search")]
+    /// Only search tasks that match this filter
+    filter: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskReopen {
+    #[arg(short, long, required_unless_present = "search", conflicts_with_all = ["search", "project", "filter"])]
+    /// ID of the task to reopen
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content among tasks completed in the last 4 weeks
+    search: Option<String>,
+
+    #[arg(short, long, requires = "search")]
+    /// Only search tasks completed in this project
+    project: Option<String>,
+
+    #[arg(short, long, requires = "search")]
+    /// Only search completed tasks that match this filter
+    filter: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskShow {
+    #[arg(short, long)]
+    /// ID of the task to show
+    id: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskMove {
+    #[arg(short, long, conflicts_with_all = ["search", "filter"])]
+    /// ID of the task to move. Prompts for a project and task when neither this nor --search is used
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content, prompting unless exactly one task contains the text
+    search: Option<String>,
+
+    #[arg(short, long)]
+    /// Only search or choose from tasks that match this filter
+    filter: Option<String>,
+
+    #[arg(short, long)]
+    /// The project to move the task to. Prompts for a project and section when neither this nor --parent is used
+    project: Option<String>,
+
+    #[arg(long)]
+    /// Name of the section in the project to move the task to
+    section: Option<String>,
+
+    #[arg(long, conflicts_with_all = ["project", "section"])]
+    /// ID of a task to move the task under as a subtask
+    parent: Option<String>,
+}
 
 #[derive(Parser, Debug, Clone)]
 struct TaskComment {
     #[arg(short, long)]
-    /// Content for comment
+    /// Content for comment. Defaults to the file name when attaching a file.
+    content: Option<String>,
+
+    #[arg(short, long)]
+    /// Path of a file to upload and attach to the comment, i.e. a log or screenshot
+    attach: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskCommentsList {
+    #[arg(short, long, conflicts_with = "search")]
+    /// ID of the task. Defaults to the last task fetched with the next command.
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content, prompting unless exactly one task contains the text
+    search: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskCommentsEdit {
+    #[arg(short, long, conflicts_with = "search")]
+    /// ID of the task, used to choose the comment when --comment is not given. Defaults to the last task fetched with the next command.
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content, prompting unless exactly one task contains the text
+    search: Option<String>,
+
+    #[arg(long)]
+    /// ID of the comment to edit
+    comment: Option<String>,
+
+    #[arg(short, long)]
+    /// New content for the comment
     content: Option<String>,
 }
 
+#[derive(Parser, Debug, Clone)]
+struct TaskCommentsDelete {
+    #[arg(short, long, conflicts_with = "search")]
+    /// ID of the task, used to choose the comment when --comment is not given. Defaults to the last task fetched with the next command.
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content, prompting unless exactly one task contains the text
+    search: Option<String>,
+
+    #[arg(long)]
+    /// ID of the comment to delete
+    comment: Option<String>,
+
+    #[arg(long)]
+    /// Skip confirmation
+    force: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskAttachments {
+    #[arg(short, long, conflicts_with = "search")]
+    /// ID of the task. Defaults to the last task fetched with the next command.
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content, prompting unless exactly one task contains the text
+    search: Option<String>,
+
+    #[arg(short, long)]
+    /// Directory to download every attached file and image into, keeping their original names
+    download: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskClone {
+    #[arg(short, long, conflicts_with = "search")]
+    /// ID of the task to copy. Defaults to the last task fetched with the next command.
+    id: Option<String>,
+
+    #[arg(short, long)]
+    /// Fuzzy search for the task by content, prompting unless exactly one task contains the text
+    search: Option<String>,
+
+    #[arg(short, long)]
+    /// The project to create the copy in. Defaults to the project of the task.
+    project: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    /// Also copy the comments on the task and its subtasks
+    comments: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TaskTemplate {
+    /// Name of the template in the config
+    name: String,
+
+    #[arg(short, long)]
+    /// The project to create the tasks in. Defaults to the project in the template, and prompts when neither is set
+    project: Option<String>,
+
+    #[arg(long)]
+    /// Fill in a variable used in the template, i.e. --var version=1.2.0. Can be used multiple times.
+    var: Vec<String>,
+}
+
 // -- LISTS --
 
 #[derive(Subcommand, Debug, Clone)]
@@ -366,6 +791,10 @@ enum ListCommands {
     /// (c) Complete a list of tasks one by one in priority order
     Process(ListProcess),
 
+    #[clap(alias = "r")]
+    /// (r) Go through tasks one by one with every action in one menu, i.e. schedule, prioritize, label and move
+    Triage(ListTriage),
+
     #[clap(alias = "z")]
     /// (z) Give every task a priority
     Prioritize(ListPrioritize),
@@ -389,6 +818,22 @@ enum ListCommands {
     #[clap(alias = "i")]
     /// (i) Create tasks from a text file, one per line using natural language. Skips empty lines.
     Import(ListImport),
+
+    #[clap(alias = "e")]
+    /// (e) Write tasks with due dates to an iCalendar (.ics) file. Timed tasks with durations become events, date only tasks become all day events.
+    ExportIcs(ListExportIcs),
+
+    #[clap(alias = "a")]
+    /// (a) Show the tasks due on a day on a timeline, with free time and overlapping tasks
+    Agenda(ListAgenda),
+
+    #[clap(alias = "w")]
+    /// (w) Show the next 7 days as a grid with a column per day. Overdue tasks are shown on today and deadlines are marked with ⚑.
+    Week(ListWeek),
+
+    #[clap(alias = "o")]
+    /// (o) Show tasks completed in a date range, grouped by the day they were completed
+    Completed(ListCompleted),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -404,6 +849,22 @@ struct ListView {
     #[arg(short = 't', long, default_value_t = SortOrder::Datetime)]
     /// Choose how results should be sorted
     sort: SortOrder,
+
+    #[arg(long, default_value_t = false)]
+    /// Nest subtasks under their parent tasks. Collapsed tasks (▸) hide their subtasks.
+    tree: bool,
+
+    #[arg(short, long)]
+    /// Show tasks under a heading for each group
+    group_by: Option<GroupBy>,
+
+    #[arg(short, long)]
+    /// Print tasks as CSV or TSV instead, for pasting into spreadsheets
+    export: Option<ExportFormat>,
+
+    #[arg(long, value_delimiter = ',', default_values_t = export::all_columns())]
+    /// Columns to include in the export, separated by commas
+    columns: Vec<ExportColumn>,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -421,6 +882,21 @@ struct ListProcess {
     sort: SortOrder,
 }
 
+#[derive(Parser, Debug, Clone)]
+struct ListTriage {
+    #[arg(short, long)]
+    /// The project containing the tasks
+    project: Option<String>,
+
+    #[arg(short, long)]
+    /// The filter containing the tasks. Can add multiple filters separated by commas.
+    filter: Option<String>,
+
+    #[arg(short = 't', long, default_value_t = SortOrder::Value)]
+    /// Choose how results should be sorted
+    sort: SortOrder,
+}
+
 #[derive(Parser, Debug, Clone)]
 struct ListTimebox {
     #[arg(short, long)]
@@ -515,16 +991,76 @@ struct ListImport {
     path: Option<String>,
 }
 
-// -- CONFIG --
-
-#[derive(Subcommand, Debug, Clone)]
-enum ConfigCommands {
-    #[clap(alias = "v")]
-    /// (v) Check to see if tod is on the latest version, returns exit code 1 if out of date. Does not need a configuration file.
-    CheckVersion(ConfigCheckVersion),
-    /// (r) Deletes the configuration file (if present). Errors if the file does not exist.
-    #[clap(alias = "r")]
-    Reset(ConfigReset),
+#[derive(Parser, Debug, Clone)]
+struct ListAgenda {
+    #[arg(short, long)]
+    /// The project containing the tasks
+    project: Option<String>,
+
+    #[arg(short, long)]
+    /// The filter containing the tasks. Defaults to the tasks due on the date.
+    filter: Option<String>,
+
+    #[arg(short, long)]
+    /// Date to show in format YYYY-MM-DD. Defaults to today.
+    date: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ListWeek {
+    #[arg(short, long)]
+    /// The project containing the tasks
+    project: Option<String>,
+
+    #[arg(short, long)]
+    /// The filter containing the tasks. Defaults to overdue tasks and the next 7 days.
+    filter: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ListCompleted {
+    #[arg(short, long)]
+    /// Only show tasks completed in this project
+    project: Option<String>,
+
+    #[arg(short, long)]
+    /// Only show completed tasks that match this filter
+    filter: Option<String>,
+
+    #[arg(short, long)]
+    /// First day to include in format YYYY-MM-DD. Defaults to 6 days before the end date.
+    since: Option<String>,
+
+    #[arg(short, long)]
+    /// Last day to include in format YYYY-MM-DD. Defaults to today. Todoist allows up to 3 months between the dates.
+    until: Option<String>,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ListExportIcs {
+    #[arg(short, long)]
+    /// The project containing the tasks
+    project: Option<String>,
+
+    #[arg(short, long)]
+    /// The filter containing the tasks. Can add multiple filters separated by commas.
+    filter: Option<String>,
+
+    #[arg(long, default_value = "tod.ics")]
+    /// Path of the .ics file to write
+    file: PathBuf,
+}
+
+// -- CONFIG --
+
+#[derive(Subcommand, Debug, Clone)]
+enum ConfigCommands {
+    #[clap(alias = "v")]
+    /// (v) Check to see if tod is on the latest version, returns exit code 1 if out of date. Does not need a configuration file.
+    CheckVersion(ConfigCheckVersion),
+    /// (r) Deletes the configuration file (if present). Errors if the file does not exist.
+    #[clap(alias = "r")]
+    Reset(ConfigReset),
 
     #[clap(alias = "tz")]
     /// (tz) Change the timezone in the configuration file
@@ -545,6 +1081,26 @@ enum ShellCommands {
     Completions(ShellCompletions),
 }
 
+#[derive(Subcommand, Debug, Clone)]
+enum TrashCommands {
+    #[clap(alias = "l")]
+    /// (l) List deleted tasks, most recent first
+    List(TrashList),
+
+    #[clap(alias = "r")]
+    /// (r) Create a deleted task again in its original project and section, along with its comments
+    Restore(TrashRestore),
+}
+
+#[derive(Parser, Debug, Clone)]
+struct TrashList {}
+
+#[derive(Parser, Debug, Clone)]
+struct TrashRestore {
+    /// ID of the deleted task, as shown by trash list
+    id: String,
+}
+
 #[derive(Subcommand, Debug, Clone)]
 enum TestCommands {
     #[clap(alias = "a")]
@@ -708,6 +1264,61 @@ async fn select_command(
                 project_empty(&config, args).await,
             )
         }
+        Commands::Project(ProjectCommands::Export(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                project_export(&config, args).await,
+            )
+        }
+        Commands::Project(ProjectCommands::Comments(ProjectCommentsCommands::List(args))) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                project_comments_list(config, args).await,
+            )
+        }
+        Commands::Project(ProjectCommands::Comments(ProjectCommentsCommands::Add(args))) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                project_comments_add(config, args).await,
+            )
+        }
+        Commands::Project(ProjectCommands::Comments(ProjectCommentsCommands::Edit(args))) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                project_comments_edit(config, args).await,
+            )
+        }
+        Commands::Project(ProjectCommands::Comments(ProjectCommentsCommands::Delete(args))) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                project_comments_delete(config, args).await,
+            )
+        }
         Commands::Project(ProjectCommands::Delete(args)) => {
             let config = match fetch_config(&cli, &tx).await {
                 Ok(config) => config,
@@ -788,6 +1399,50 @@ async fn select_command(
                 task_complete(config, args).await,
             )
         }
+        Commands::Task(TaskCommands::Delete(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_delete(config, args).await,
+            )
+        }
+        Commands::Task(TaskCommands::Reopen(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_reopen(config, args).await,
+            )
+        }
+        Commands::Task(TaskCommands::Show(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_show(config, args).await,
+            )
+        }
+        Commands::Task(TaskCommands::Move(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_move(config, args).await,
+            )
+        }
         Commands::Task(TaskCommands::Comment(args)) => {
             let config = match fetch_config(&cli, &tx).await {
                 Ok(config) => config,
@@ -799,6 +1454,72 @@ async fn select_command(
                 task_comment(config, args).await,
             )
         }
+        Commands::Task(TaskCommands::Comments(TaskCommentsCommands::List(args))) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_comments_list(config, args).await,
+            )
+        }
+        Commands::Task(TaskCommands::Comments(TaskCommentsCommands::Edit(args))) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_comments_edit(config, args).await,
+            )
+        }
+        Commands::Task(TaskCommands::Comments(TaskCommentsCommands::Delete(args))) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_comments_delete(config, args).await,
+            )
+        }
+        Commands::Task(TaskCommands::Attachments(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_attachments(config, args).await,
+            )
+        }
+        Commands::Task(TaskCommands::Clone(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_clone(config, args).await,
+            )
+        }
+        Commands::Task(TaskCommands::Template(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                task_template(config, args).await,
+            )
+        }
 
         // List
         Commands::List(ListCommands::View(args)) => {
@@ -823,6 +1544,17 @@ async fn select_command(
                 list_process(config, args).await,
             )
         }
+        Commands::List(ListCommands::Triage(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                list_triage(config, args).await,
+            )
+        }
         Commands::List(ListCommands::Prioritize(args)) => {
             let config = match fetch_config(&cli, &tx).await {
                 Ok(config) => config,
@@ -889,6 +1621,50 @@ async fn select_command(
                 list_import(config, args).await,
             )
         }
+        Commands::List(ListCommands::Agenda(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                list_agenda(config, args).await,
+            )
+        }
+        Commands::List(ListCommands::Week(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                list_week(config, args).await,
+            )
+        }
+        Commands::List(ListCommands::Completed(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                list_completed(config, args).await,
+            )
+        }
+        Commands::List(ListCommands::ExportIcs(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                list_export_ics(config, args).await,
+            )
+        }
 
         // Config
         Commands::Config(ConfigCommands::CheckVersion(args)) => {
@@ -946,15 +1722,65 @@ async fn select_command(
                 test_all(config, args).await,
             )
         }
-    }
-}
 
-async fn auth_login(config: Config, _args: &AuthLogin) -> Result<String, Error> {
-    let mut config = config;
-    oauth::login(&mut config, None).await
-}
+        // Stats
+        Commands::Stats(args) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                stats(config, args).await,
+            )
+        }
 
-async fn shell_completions(args: &ShellCompletions) -> Result<String, Error> {
+        // Undo
+        Commands::Undo(args) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                undo(config, args).await,
+            )
+        }
+
+        // Trash
+        Commands::Trash(TrashCommands::List(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                trash_list(config, args).await,
+            )
+        }
+        Commands::Trash(TrashCommands::Restore(args)) => {
+            let config = match fetch_config(&cli, &tx).await {
+                Ok(config) => config,
+                Err(e) => return (true, true, Err(e)),
+            };
+            (
+                config.bell_on_success,
+                config.bell_on_failure,
+                trash_restore(config, args).await,
+            )
+        }
+    }
+}
+
+async fn auth_login(config: Config, _args: &AuthLogin) -> Result<String, Error> {
+    let mut config = config;
+    oauth::login(&mut config, None).await
+}
+
+async fn shell_completions(args: &ShellCompletions) -> Result<String, Error> {
     shell::generate_completions(args.shell);
 
     Ok(String::new())
@@ -979,7 +1805,10 @@ async fn task_quick_add(config: Config, args: &TaskQuickAdd) -> Result<String, E
     } else {
         (content, None)
     };
-    todoist::quick_create_task(&config, &content, reminder).await?;
+    let task = todoist::quick_create_task(&config, &content, reminder).await?;
+    if let Some(result) = output::maybe_single(&config, &task) {
+        return result;
+    }
     Ok(color::green_string("✓"))
 }
 
@@ -992,6 +1821,13 @@ async fn task_create(config: Config, args: &TaskCreate) -> Result<String, Error>
     if no_flags_used(args) {
         let options = tasks::create_task_attributes();
         let selections = input::multi_select(input::ATTRIBUTES, options, config.mock_select)?;
+        let with_duration = selections.contains(&TaskAttribute::Duration);
+        if with_duration && !selections.contains(&TaskAttribute::Due) {
+            return Err(Error::new(
+                "task_create",
+                "A duration requires a due time, select a due date as well",
+            ));
+        }
 
         let content = fetch_string(None, &config, input::CONTENT)?;
 
@@ -1018,7 +1854,18 @@ async fn task_create(config: Config, args: &TaskCreate) -> Result<String, Error>
             match datetime_input {
                 DateTimeInput::Skip => unreachable!(),
                 DateTimeInput::Complete => unreachable!(),
+                DateTimeInput::None if with_duration => {
+                    return Err(Error::new(
+                        "task_create",
+                        "A duration requires a due time, choose a date",
+                    ));
+                }
                 DateTimeInput::None => None,
+                // Dates from the date picker have no time for the duration to start at
+                DateTimeInput::Text(date) if with_duration && time::is_date(&date) => {
+                    let time = fetch_string(None, &config, input::TIME)?;
+                    Some(format!("{date} {time}"))
+                }
                 DateTimeInput::Text(datetime) => Some(datetime),
             }
         } else {
@@ -1031,131 +1878,708 @@ async fn task_create(config: Config, args: &TaskCreate) -> Result<String, Error>
         } else {
             Vec::new()
         }
-        .into_iter()
-        .map(|l| l.name.to_owned())
-        .collect::<Vec<String>>();
+        .into_iter()
+        .map(|l| l.name.to_owned())
+        .collect::<Vec<String>>();
+
+        let deadline = if selections.contains(&TaskAttribute::Deadline) {
+            Some(input::date()?)
+        } else {
+            None
+        };
+
+        let duration = if with_duration {
+            Some(fetch_string(None, &config, input::DURATION)?.parse::<u32>()?)
+        } else {
+            None
+        };
+
+        let project = match fetch_project(args.project.as_deref(), &config).await? {
+            Flag::Project(project) => project,
+            _ => unreachable!(),
+        };
+
+        let parent = if selections.contains(&TaskAttribute::Parent) {
+            let tasks = todoist::all_tasks_by_project(&config, &project, None).await?;
+            Some(input::select(input::TASK, tasks, config.mock_select)?)
+        } else {
+            None
+        };
+
+        // Subtasks are placed in the section of their parent
+        let section = if is_no_sections(args, &config) || parent.is_some() {
+            None
+        } else {
+            sections::select_section(&config, &project).await?
+        };
+
+        todoist::create_task(
+            &config,
+            &content,
+            &project,
+            section,
+            priority,
+            &description,
+            due.as_deref(),
+            &labels,
+            parent.as_ref().map(|parent| parent.id.as_str()),
+            deadline.as_deref(),
+            duration,
+        )
+        .await?;
+    } else {
+        let TaskCreate {
+            project,
+            due,
+            description,
+            content,
+            priority,
+            label: labels,
+            no_section: _no_section,
+            parent,
+            deadline,
+            duration,
+        } = args;
+        if duration.is_some() && due.is_none() {
+            return Err(Error::new(
+                "task_create",
+                "A duration requires a due time, use --due as well",
+            ));
+        }
+        let project = match fetch_project(project.as_deref(), &config).await? {
+            Flag::Project(project) => project,
+            _ => unreachable!(),
+        };
+
+        let parent = match parent {
+            Some(parent) => Some(fetch_parent_task(&config, &project, parent).await?),
+            None => None,
+        };
+
+        // Subtasks are placed in the section of their parent
+        let section = if is_no_sections(args, &config) || parent.is_some() {
+            None
+        } else {
+            sections::select_section(&config, &project).await?
+        };
+        let content = fetch_string(content.as_deref(), &config, input::CONTENT)?;
+        let priority = fetch_priority(priority, &config)?;
+
+        todoist::create_task(
+            &config,
+            &content,
+            &project,
+            section,
+            priority,
+            description,
+            due.as_deref(),
+            labels,
+            parent.as_ref().map(|parent| parent.id.as_str()),
+            deadline.as_deref(),
+            *duration,
+        )
+        .await?;
+    }
+    Ok(color::green_string("✓"))
+}
+
+fn no_flags_used(args: &TaskCreate) -> bool {
+    let TaskCreate {
+        project,
+        due,
+        description,
+        content,
+        no_section: _no_section,
+        priority,
+        label,
+        parent,
+        deadline,
+        duration,
+    } = args;
+
+    project.is_none()
+        && due.is_none()
+        && description.is_empty()
+        && content.is_none()
+        && priority.is_none()
+        && label.is_empty()
+        && parent.is_none()
+        && deadline.is_none()
+        && duration.is_none()
+}
+
+/// Find the parent for a new subtask in a project, by ID or else by searching
+async fn fetch_parent_task(
+    config: &Config,
+    project: &Project,
+    parent: &str,
+) -> Result<Task, Error> {
+    let mut tasks = todoist::all_tasks_by_project(config, project, None).await?;
+    match tasks.iter().position(|task| task.id == parent) {
+        Some(index) => Ok(tasks.remove(index)),
+        None => select_search_result(config, tasks, parent),
+    }
+}
+
+async fn task_edit(config: Config, args: &TaskEdit) -> Result<String, Error> {
+    if let Some(id) = &args.id {
+        return task_edit_by_id(config, id, args).await;
+    }
+    if !no_edit_flags_used(args) {
+        return Err(Error::new(
+            "task_edit",
+            "Must use --id to edit a task with flags",
+        ));
+    }
+
+    let TaskEdit {
+        project, filter, ..
+    } = args;
+    match fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await? {
+        Flag::Project(project) => projects::edit_task(&config, &project).await,
+        Flag::Filter(filter) => filters::edit_task(&config, filter).await,
+    }
+}
+
+/// Apply each of the edit flags to a task without prompting
+async fn task_edit_by_id(config: Config, id: &str, args: &TaskEdit) -> Result<String, Error> {
+    if no_edit_flags_used(args) && args.project.is_none() {
+        return Err(Error::new(
+            "task_edit",
+            "Nothing to edit. Use flags such as --content or --due along with --id",
+        ));
+    }
+    if args.filter.is_some() {
+        return Err(Error::new("task_edit", "Cannot use --filter with --id"));
+    }
+    let TaskEdit {
+        project,
+        filter: _filter,
+        id: _id,
+        content,
+        description,
+        priority,
+        due,
+        deadline,
+        duration,
+        add_label,
+        remove_label,
+        section,
+    } = args;
+
+    let task = todoist::get_task(&config, id).await?;
+    if duration.is_some() && due.is_none() && !task.has_due_time(&config)? {
+        return Err(Error::new(
+            "task_edit",
+            "A duration requires a due time, use --due as well",
+        ));
+    }
+
+    if let Some(content) = content {
+        todoist::update_task_content(&config, &task, content.clone(), true).await?;
+    }
+    if let Some(description) = description {
+        todoist::update_task_description(&config, &task, description.clone(), true).await?;
+    }
+    if let Some(priority) = priority::from_integer(priority) {
+        todoist::update_task_priority(&config, &task, &priority, true).await?;
+    }
+    match (due, duration) {
+        (Some(due), duration) => {
+            todoist::update_task_due_natural_language(&config, &task, due.clone(), *duration, true)
+                .await?;
+        }
+        (None, Some(duration)) => {
+            todoist::update_task_duration(&config, &task, *duration, true).await?;
+        }
+        (None, None) => (),
+    }
+    if let Some(deadline) = deadline {
+        todoist::update_task_deadline(&config, &task, Some(deadline.clone()), true).await?;
+    }
+    if !add_label.is_empty() || !remove_label.is_empty() {
+        let mut labels = task
+            .labels
+            .iter()
+            .filter(|label| !remove_label.contains(label))
+            .cloned()
+            .collect::<Vec<String>>();
+        for label in add_label {
+            if !labels.contains(label) {
+                labels.push(label.clone());
+            }
+        }
+        todoist::update_task_labels(&config, &task, labels, true).await?;
+    }
+
+    let project = match project {
+        Some(name) => match fetch_project(Some(name), &config).await? {
+            Flag::Project(project) => Some(project),
+            _ => unreachable!(),
+        },
+        None => None,
+    };
+    match (project, section) {
+        (project, Some(section)) => {
+            let project = match project {
+                Some(project) => project,
+                None => config
+                    .projects()
+                    .await?
+                    .into_iter()
+                    .find(|project| project.id == task.project_id)
+                    .ok_or_else(|| {
+                        Error::new(
+                            "task_edit",
+                            "The task's project is not in config, use --project to choose the section's project",
+                        )
+                    })?,
+            };
+            let section = sections::find_section(&config, &project, section).await?;
+            todoist::move_task_to_section(&config, &task, &section, true).await?;
+        }
+        (Some(project), None) => {
+            todoist::move_task_to_project(&config, &task, &project, true).await?;
+        }
+        (None, None) => (),
+    }
+
+    Ok(color::green_string("Task edited successfully"))
+}
+
+fn no_edit_flags_used(args: &TaskEdit) -> bool {
+    let TaskEdit {
+        project: _project,
+        filter: _filter,
+        id: _id,
+        content,
+        description,
+        priority,
+        due,
+        deadline,
+        duration,
+        add_label,
+        remove_label,
+        section,
+    } = args;
+
+    content.is_none()
+        && description.is_none()
+        && priority.is_none()
+        && due.is_none()
+        && deadline.is_none()
+        && duration.is_none()
+        && add_label.is_empty()
+        && remove_label.is_empty()
+        && section.is_none()
+}
+
+async fn task_next(config: Config, args: &TaskNext) -> Result<String, Error> {
+    let TaskNext { project, filter } = args;
+    match fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await? {
+        Flag::Project(project) => projects::next_task(config, &project).await,
+        Flag::Filter(filter) => filters::next_task(&config, &filter).await,
+    }
+}
+
+async fn task_complete(config: Config, args: &TaskComplete) -> Result<String, Error> {
+    let TaskComplete {
+        id,
+        search,
+        project,
+        filter,
+    } = args;
+    let task = match (id, search) {
+        (None, None) => config.next_task().ok_or_else(|| {
+            Error::new(
+                "task_complete",
+                "There is nothing to complete. A task must first be marked as 'next', or use --id or --search.",
+            )
+        })?,
+        _ => {
+            fetch_open_task(
+                &config,
+                id.as_deref(),
+                search.as_deref(),
+                project.as_deref(),
+                filter.as_deref(),
+            )
+            .await?
+        }
+    };
+    todoist::complete_task(&config, &task, true).await?;
+
+    Ok(color::green_string("Task completed successfully"))
+}
+
+async fn task_delete(config: Config, args: &TaskDelete) -> Result<String, Error> {
+    let TaskDelete {
+        id,
+        search,
+        project,
+        filter,
+    } = args;
+    let task = fetch_open_task(
+        &config,
+        id.as_deref(),
+        search.as_deref(),
+        project.as_deref(),
+        filter.as_deref(),
+    )
+    .await?;
+    todoist::delete_task(&config, &task, true).await?;
+
+    Ok(color::green_string("Task deleted successfully"))
+}
+
+async fn task_reopen(config: Config, args: &TaskReopen) -> Result<String, Error> {
+    let TaskReopen {
+        id,
+        search,
+        project,
+        filter,
+    } = args;
+    let task = match (id, search) {
+        (Some(id), None) => todoist::get_task(&config, id).await?,
+        (None, Some(search)) => {
+            let until = time::naive_date_today(&config)?;
+            let since = until - chrono::Duration::weeks(REOPEN_SEARCH_WEEKS);
+            let flag = match (project, filter) {
+                (None, None) => None,
+                _ => Some(
+                    fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await?,
+                ),
+            };
+            let (project, filter) = match &flag {
+                None => (None, None),
+                Some(Flag::Project(project)) => (Some(project), None),
+                Some(Flag::Filter(filter)) => (None, Some(filter.as_str())),
+            };
+            let tasks =
+                todoist::all_completed_tasks(&config, since, until, project, filter).await?;
+            select_search_result(&config, tasks, search)?
+        }
+        _ => return Err(Error::new("task_reopen", ID_OR_SEARCH_ERR)),
+    };
+    todoist::reopen_task(&config, &task, true).await?;
+
+    Ok(color::green_string("Task reopened successfully"))
+}
+
+async fn task_show(config: Config, args: &TaskShow) -> Result<String, Error> {
+    let TaskShow { id } = args;
+    tasks::show(&config, id).await
+}
+
+async fn task_move(config: Config, args: &TaskMove) -> Result<String, Error> {
+    let TaskMove {
+        id,
+        search,
+        filter,
+        project,
+        section,
+        parent,
+    } = args;
+    let task = match (id, search) {
+        (None, None) => {
+            let tasks = match filter {
+                Some(filter) => todoist::all_tasks_by_filters(&config, filter)
+                    .await?
+                    .into_iter()
+                    .flat_map(|(_, tasks)| tasks)
+                    .collect(),
+                None => {
+                    let source = match fetch_project(None, &config).await? {
+                        Flag::Project(project) => project,
+                        _ => unreachable!(),
+                    };
+                    todoist::all_tasks_by_project(&config, &source, None).await?
+                }
+            };
+            input::select(input::TASK, tasks, config.mock_select)?
+        }
+        _ => {
+            fetch_open_task(
+                &config,
+                id.as_deref(),
+                search.as_deref(),
+                None,
+                filter.as_deref(),
+            )
+            .await?
+        }
+    };
+
+    if let Some(parent) = parent {
+        let parent = todoist::get_task(&config, parent).await?;
+        check_new_parent(&config, &task, &parent).await?;
+        todoist::move_task_to_parent(&config, &task, &parent, true).await?;
+        return Ok(color::green_string(&format!(
+            "Moved '{}' under '{}'",
+            task.content, parent.content
+        )));
+    }
+
+    let interactive = project.is_none();
+    let project = match fetch_project(project.as_deref(), &config).await? {
+        Flag::Project(project) => project,
+        _ => unreachable!(),
+    };
+    let section = match section {
+        Some(name) => Some(sections::find_section(&config, &project, name).await?),
+        None if interactive && !config.no_sections.unwrap_or_default() => {
+            sections::select_section(&config, &project).await?
+        }
+        None => None,
+    };
+
+    // Subtasks move along with their parent
+    match section {
+        Some(section) => todoist::move_task_to_section(&config, &task, &section, true).await?,
+        None => todoist::move_task_to_project(&config, &task, &project, true).await?,
+    };
+
+    Ok(color::green_string(&format!(
+        "Moved '{}' to '{}'",
+        task.content, project.name
+    )))
+}
+
+/// A task can't be moved under itself or under one of its own subtasks
+async fn check_new_parent(config: &Config, task: &Task, parent: &Task) -> Result<(), Error> {
+    let mut ancestor = parent.clone();
+    loop {
+        if ancestor.id == task.id {
+            return Err(Error::new(
+                "task_move",
+                "Cannot move a task under itself or one of its subtasks",
+            ));
+        }
+        match ancestor.parent_id {
+            Some(id) => ancestor = todoist::get_task(config, &id).await?,
+            None => return Ok(()),
+        }
+    }
+}
+
+async fn task_comment(config: Config, args: &TaskComment) -> Result<String, Error> {
+    let TaskComment { content, attach } = args;
+    match (config.next_task(), attach) {
+        (Some(task), None) => {
+            let content = fetch_string(content.as_deref(), &config, input::CONTENT)?;
+            todoist::create_comment(&config, &task, content, true).await?;
+            Ok(color::green_string("Comment created successfully"))
+        }
+        (Some(task), Some(path)) => {
+            let attachment = todoist::upload_file(&config, path).await?;
+            let content = content
+                .clone()
+                .unwrap_or_else(|| attachment.file_name.clone());
+            let attachment = Attachment::File(attachment);
+            todoist::create_comment_with_attachment(&config, &task, content, &attachment, true)
+                .await?;
+            Ok(color::green_string("Comment created successfully"))
+        }
+        (None, _) => Err(Error::new(
+            "task_comment",
+            "There is nothing to comment on. A task must first be marked as 'next'.",
+        )),
+    }
+}
 
-        let project = match fetch_project(args.project.as_deref(), &config).await? {
-            Flag::Project(project) => project,
-            _ => unreachable!(),
-        };
+async fn task_comments_list(config: Config, args: &TaskCommentsList) -> Result<String, Error> {
+    let TaskCommentsList { id, search } = args;
+    let comments = task_comments(&config, id.as_deref(), search.as_deref()).await?;
+    list_comments(&config, &comments)
+}
 
-        let section = if is_no_sections(args, &config) {
-            None
-        } else {
-            sections::select_section(&config, &project).await?
-        };
+async fn task_attachments(config: Config, args: &TaskAttachments) -> Result<String, Error> {
+    let TaskAttachments {
+        id,
+        search,
+        download,
+    } = args;
+    let task = fetch_task_or_next(&config, id.as_deref(), search.as_deref()).await?;
+    let comments = todoist::all_comments(&config, &task, None).await?;
+    if let Some(dir) = download {
+        return comments::download_attachments(&config, &comments, dir).await;
+    }
 
-        todoist::create_task(
-            &config,
-            &content,
-            &project,
-            section,
-            priority,
-            &description,
-            due.as_deref(),
-            &labels,
-        )
-        .await?;
+    let attachments = comments
+        .iter()
+        .filter_map(|comment| comment.file_attachment.as_ref())
+        .map(|attachment| format!("- {} {}", attachment.label(), attachment.url()))
+        .collect::<Vec<String>>();
+    if attachments.is_empty() {
+        Ok(String::from("No attachments on this task"))
     } else {
-        let TaskCreate {
-            project,
-            due,
-            description,
-            content,
-            priority,
-            label: labels,
-            no_section: _no_section,
-        } = args;
-        let project = match fetch_project(project.as_deref(), &config).await? {
+        Ok(attachments.join("\n"))
+    }
+}
+
+async fn task_clone(config: Config, args: &TaskClone) -> Result<String, Error> {
+    let TaskClone {
+        id,
+        search,
+        project,
+        comments,
+    } = args;
+    let task = fetch_task_or_next(&config, id.as_deref(), search.as_deref()).await?;
+    let project = match project {
+        Some(_) => match fetch_project(project.as_deref(), &config).await? {
             Flag::Project(project) => project,
             _ => unreachable!(),
-        };
+        },
+        None => config
+            .projects()
+            .await?
+            .into_iter()
+            .find(|project| project.id == task.project_id)
+            .ok_or_else(|| {
+                Error::new(
+                    "task_clone",
+                    "The task's project is not in config, use --project or import it with tod project import",
+                )
+            })?,
+    };
 
-        let section = if is_no_sections(args, &config) {
-            None
-        } else {
-            sections::select_section(&config, &project).await?
-        };
-        let content = fetch_string(content.as_deref(), &config, input::CONTENT)?;
-        let priority = fetch_priority(priority, &config)?;
+    let tasks = tasks::duplicate(&config, &task, &project, *comments).await?;
+    if let Some(result) = output::maybe_list(&config, &tasks) {
+        return result;
+    }
+    Ok(color::green_string(&format!(
+        "Copied '{}' and {} subtask(s) to '{}'",
+        task.content,
+        tasks.len().saturating_sub(1),
+        project.name
+    )))
+}
+
+async fn task_template(config: Config, args: &TaskTemplate) -> Result<String, Error> {
+    let TaskTemplate { name, project, var } = args;
+    let template = config.template(name)?;
+    let vars = templates::parse_vars(var)?;
+    let project_name = project.as_deref().or(template.project.as_deref());
+    let project = match fetch_project(project_name, &config).await? {
+        Flag::Project(project) => project,
+        _ => unreachable!(),
+    };
 
-        todoist::create_task(
-            &config,
-            &content,
-            &project,
-            section,
-            priority,
-            description,
-            due.as_deref(),
-            labels,
-        )
-        .await?;
+    let tasks = templates::create(&config, template, &project, &vars).await?;
+    if let Some(result) = output::maybe_list(&config, &tasks) {
+        return result;
     }
-    Ok(color::green_string("✓"))
+    let contents = tasks
+        .iter()
+        .map(|task| format!("- {}", task.content))
+        .collect::<Vec<String>>()
+        .join("\n");
+    Ok(format!(
+        "Created {} task(s) from template '{name}'\n{contents}",
+        tasks.len()
+    ))
+}
+
+async fn task_comments_edit(config: Config, args: &TaskCommentsEdit) -> Result<String, Error> {
+    let TaskCommentsEdit {
+        id,
+        search,
+        comment,
+        content,
+    } = args;
+    let comments = task_comments(&config, id.as_deref(), search.as_deref());
+    let comment = fetch_comment(&config, comment.as_deref(), comments).await?;
+    edit_comment(&config, &comment, content.as_deref()).await
 }
 
-fn no_flags_used(args: &TaskCreate) -> bool {
-    let TaskCreate {
-        project,
-        due,
-        description,
-        content,
-        no_section: _no_section,
-        priority,
-        label,
+async fn task_comments_delete(config: Config, args: &TaskCommentsDelete) -> Result<String, Error> {
+    let TaskCommentsDelete {
+        id,
+        search,
+        comment,
+        force,
     } = args;
+    let comments = task_comments(&config, id.as_deref(), search.as_deref());
+    let comment = fetch_comment(&config, comment.as_deref(), comments).await?;
+    delete_comment(&config, &comment, *force).await
+}
 
-    project.is_none()
-        && due.is_none()
-        && description.is_empty()
-        && content.is_none()
-        && priority.is_none()
-        && label.is_empty()
+/// Comments on the task given by ID or search, or else on the last task fetched with the next command
+async fn task_comments(
+    config: &Config,
+    id: Option<&str>,
+    search: Option<&str>,
+) -> Result<Vec<Comment>, Error> {
+    let task = fetch_task_or_next(config, id, search).await?;
+    todoist::all_comments(config, &task, None).await
 }
 
-async fn task_edit(config: Config, args: &TaskEdit) -> Result<String, Error> {
-    let TaskEdit { project, filter } = args;
-    match fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await? {
-        Flag::Project(project) => projects::edit_task(&config, &project).await,
-        Flag::Filter(filter) => filters::edit_task(&config, filter).await,
+/// The task given by ID or search, or else the last task fetched with the next command
+async fn fetch_task_or_next(
+    config: &Config,
+    id: Option<&str>,
+    search: Option<&str>,
+) -> Result<Task, Error> {
+    match (id, search) {
+        (None, None) => config.next_task().ok_or_else(|| {
+            Error::new(
+                "fetch_task_or_next",
+                "There is no task to use. Use --id or --search, or mark a task as 'next' first.",
+            )
+        }),
+        _ => fetch_open_task(config, id, search, None, None).await,
     }
 }
-async fn task_next(config: Config, args: &TaskNext) -> Result<String, Error> {
-    let TaskNext { project, filter } = args;
-    match fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await? {
-        Flag::Project(project) => projects::next_task(config, &project).await,
-        Flag::Filter(filter) => filters::next_task(&config, &filter).await,
+
+fn list_comments(config: &Config, comments: &[Comment]) -> Result<String, Error> {
+    if let Some(result) = output::maybe_list(config, comments) {
+        return result;
     }
+    comments::render_all(config, comments)
 }
 
-async fn task_complete(config: Config, _args: &TaskComplete) -> Result<String, Error> {
-    match config.next_task() {
-        Some(task) => {
-            todoist::complete_task(&config, &task, true).await?;
+/// The comment given by ID, or else one chosen from the comments. These are only fetched when there is no ID.
+async fn fetch_comment(
+    config: &Config,
+    comment_id: Option<&str>,
+    comments: impl Future<Output = Result<Vec<Comment>, Error>>,
+) -> Result<Comment, Error> {
+    match comment_id {
+        Some(comment_id) => todoist::get_comment(config, comment_id).await,
+        None => select_comment(config, comments.await?),
+    }
+}
 
-            Ok(color::green_string("Task completed successfully"))
-        }
-        None => Err(Error::new(
-            "task_complete",
-            "There is nothing to complete. A task must first be marked as 'next'.",
-        )),
+fn select_comment(config: &Config, comments: Vec<Comment>) -> Result<Comment, Error> {
+    if comments.is_empty() {
+        return Err(Error::new("select_comment", "There are no comments"));
     }
+    input::select(input::COMMENT_SELECT, comments, config.mock_select)
 }
 
-async fn task_comment(config: Config, args: &TaskComment) -> Result<String, Error> {
-    let TaskComment { content } = args;
-    match config.next_task() {
-        Some(task) => {
-            let content = fetch_string(content.as_deref(), &config, input::CONTENT)?;
-            todoist::create_comment(&config, &task, content, true).await?;
-            Ok(color::green_string("Comment created successfully"))
+async fn edit_comment(
+    config: &Config,
+    comment: &Comment,
+    content: Option<&str>,
+) -> Result<String, Error> {
+    let content = match content {
+        Some(content) => content.to_string(),
+        None => input::string_with_default("Enter new content:", &comment.content)?,
+    };
+    todoist::update_comment(config, comment, content, true).await?;
+    Ok(color::green_string("Comment updated successfully"))
+}
+
+async fn delete_comment(config: &Config, comment: &Comment, force: bool) -> Result<String, Error> {
+    if !force {
+        let options = vec![input::CANCEL, input::DELETE];
+        let desc = format!("Delete comment '{}'?", comment.content);
+        if input::select(&desc, options, config.mock_select)? == input::CANCEL {
+            return Ok("Cancelled".into());
         }
-        None => Err(Error::new(
-            "task_comment",
-            "There is nothing to comment on. A task must first be marked as 'next'.",
-        )),
     }
+    todoist::delete_comment(config, comment, true).await?;
+    Ok(color::green_string("Comment deleted successfully"))
 }
 
 // --- PROJECT ---
@@ -1252,6 +2676,76 @@ async fn project_import(config: Config, args: &ProjectImport) -> Result<String,
     projects::import(&mut config, auto).await
 }
 
+async fn project_export(config: &Config, args: &ProjectExport) -> Result<String, Error> {
+    let ProjectExport {
+        project,
+        format,
+        file,
+    } = args;
+    let project = match fetch_project(project.as_deref(), config).await? {
+        Flag::Project(project) => project,
+        _ => unreachable!(),
+    };
+
+    projects::export(config, &project, format, file.as_deref()).await
+}
+
+async fn project_comments_list(
+    config: Config,
+    args: &ProjectCommentsList,
+) -> Result<String, Error> {
+    let ProjectCommentsList { project } = args;
+    let comments = project_comments(&config, project.as_deref()).await?;
+    list_comments(&config, &comments)
+}
+
+async fn project_comments_add(config: Config, args: &ProjectCommentsAdd) -> Result<String, Error> {
+    let ProjectCommentsAdd { project, content } = args;
+    let project = match fetch_project(project.as_deref(), &config).await? {
+        Flag::Project(project) => project,
+        _ => unreachable!(),
+    };
+    let content = fetch_string(content.as_deref(), &config, input::CONTENT)?;
+    todoist::create_project_comment(&config, &project, content, true).await?;
+    Ok(color::green_string("Comment created successfully"))
+}
+
+async fn project_comments_edit(
+    config: Config,
+    args: &ProjectCommentsEdit,
+) -> Result<String, Error> {
+    let ProjectCommentsEdit {
+        project,
+        comment,
+        content,
+    } = args;
+    let comments = project_comments(&config, project.as_deref());
+    let comment = fetch_comment(&config, comment.as_deref(), comments).await?;
+    edit_comment(&config, &comment, content.as_deref()).await
+}
+
+async fn project_comments_delete(
+    config: Config,
+    args: &ProjectCommentsDelete,
+) -> Result<String, Error> {
+    let ProjectCommentsDelete {
+        project,
+        comment,
+        force,
+    } = args;
+    let comments = project_comments(&config, project.as_deref());
+    let comment = fetch_comment(&config, comment.as_deref(), comments).await?;
+    delete_comment(&config, &comment, *force).await
+}
+
+async fn project_comments(config: &Config, project: Option<&str>) -> Result<Vec<Comment>, Error> {
+    let project = match fetch_project(project, config).await? {
+        Flag::Project(project) => project,
+        _ => unreachable!(),
+    };
+    todoist::all_project_comments(config, &project, None).await
+}
+
 async fn project_empty(config: &Config, args: &ProjectEmpty) -> Result<String, Error> {
     let ProjectEmpty { project } = args;
     let project = match fetch_project(project.as_deref(), config).await? {
@@ -1285,10 +2779,17 @@ async fn list_view(config: Config, args: &ListView) -> Result<String, Error> {
         project,
         filter,
         sort,
+        tree,
+        group_by,
+        export,
+        columns,
     } = args;
 
     let flag = fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await?;
-    lists::view(&mut config, flag, sort).await
+    match export {
+        Some(format) => lists::export(&config, flag, sort, format, columns).await,
+        None => lists::view(&mut config, flag, sort, *tree, group_by.as_ref()).await,
+    }
 }
 
 async fn list_label(config: Config, args: &ListLabel) -> Result<String, Error> {
@@ -1313,6 +2814,16 @@ async fn list_process(config: Config, args: &ListProcess) -> Result<String, Erro
     lists::process(&config, flag, sort).await
 }
 
+async fn list_triage(config: Config, args: &ListTriage) -> Result<String, Error> {
+    let ListTriage {
+        project,
+        filter,
+        sort,
+    } = args;
+    let flag = fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await?;
+    lists::triage(&config, flag, sort).await
+}
+
 async fn list_timebox(config: Config, args: &ListTimebox) -> Result<String, Error> {
     let ListTimebox {
         project,
@@ -1339,6 +2850,84 @@ async fn list_import(config: Config, args: &ListImport) -> Result<String, Error>
     lists::import(&config, &file_path).await
 }
 
+async fn list_agenda(config: Config, args: &ListAgenda) -> Result<String, Error> {
+    let ListAgenda {
+        project,
+        filter,
+        date,
+    } = args;
+    let date = match date {
+        Some(date) => time::date_from_str(date, time::timezone_from_str(&config.get_timezone()?)?)?,
+        None => time::naive_date_today(&config)?,
+    };
+    let flag = match (project, filter) {
+        (None, None) => Flag::Filter(lists::agenda::filter_for_date(&config, date)?),
+        _ => fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await?,
+    };
+    lists::agenda(&config, flag, date).await
+}
+
+async fn list_week(config: Config, args: &ListWeek) -> Result<String, Error> {
+    let ListWeek { project, filter } = args;
+    let flag = match (project, filter) {
+        (None, None) => Flag::Filter(lists::week::FILTER.into()),
+        _ => fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await?,
+    };
+    lists::week(&config, flag).await
+}
+
+async fn list_completed(config: Config, args: &ListCompleted) -> Result<String, Error> {
+    let ListCompleted {
+        project,
+        filter,
+        since,
+        until,
+    } = args;
+    let tz = time::timezone_from_str(&config.get_timezone()?)?;
+    let until = match until {
+        Some(date) => time::date_from_str(date, tz)?,
+        None => time::naive_date_today(&config)?,
+    };
+    let since = match since {
+        Some(date) => time::date_from_str(date, tz)?,
+        None => until - chrono::Duration::days(6),
+    };
+    let flag = match (project, filter) {
+        (None, None) => None,
+        _ => Some(fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await?),
+    };
+    lists::completed(&config, flag, since, until).await
+}
+
+async fn stats(config: Config, args: &Stats) -> Result<String, Error> {
+    let Stats { weeks } = args;
+    stats::report(&config, *weeks).await
+}
+
+async fn undo(config: Config, args: &Undo) -> Result<String, Error> {
+    let Undo { last } = args;
+    journal::undo(&config, *last).await
+}
+
+async fn trash_list(config: Config, _args: &TrashList) -> Result<String, Error> {
+    trash::list(&config).await
+}
+
+async fn trash_restore(config: Config, args: &TrashRestore) -> Result<String, Error> {
+    let TrashRestore { id } = args;
+    trash::restore(&config, id).await
+}
+
+async fn list_export_ics(config: Config, args: &ListExportIcs) -> Result<String, Error> {
+    let ListExportIcs {
+        project,
+        filter,
+        file,
+    } = args;
+    let flag = fetch_project_or_filter(project.as_deref(), filter.as_deref(), &config).await?;
+    lists::export_ics(&config, flag, file).await
+}
+
 fn select_file(path_or_file: String, config: &Config) -> Result<String, Error> {
     let path = Path::new(&path_or_file);
     if Path::is_dir(path) {
@@ -1503,6 +3092,7 @@ async fn fetch_config(cli: &Cli, tx: &UnboundedSender<Error>) -> Result<Config,
         verbose,
         config: config_path,
         timeout,
+        output,
         command: _,
     } = cli;
 
@@ -1510,7 +3100,9 @@ async fn fetch_config(cli: &Cli, tx: &UnboundedSender<Error>) -> Result<Config,
     let verbose = verbose.to_owned();
     let timeout = timeout.to_owned();
 
-    let config = config::get_or_create(config_path, verbose, timeout, tx).await?;
+    let config = config::get_or_create(config_path, verbose, timeout, tx)
+        .await?
+        .with_output(*output);
 
     let async_config = config.clone();
 
@@ -1589,6 +3181,55 @@ async fn fetch_project_or_filter(
     }
 }
 
+/// Get an open task by ID, or by searching the tasks in a project, filter, or everywhere
+async fn fetch_open_task(
+    config: &Config,
+    id: Option<&str>,
+    search: Option<&str>,
+    project: Option<&str>,
+    filter: Option<&str>,
+) -> Result<Task, Error> {
+    match (id, search) {
+        (Some(id), None) => todoist::get_task(config, id).await,
+        (None, Some(search)) => {
+            let tasks = match (project, filter) {
+                (None, None) => todoist::all_tasks(config).await?,
+                _ => match fetch_project_or_filter(project, filter, config).await? {
+                    Flag::Project(project) => {
+                        todoist::all_tasks_by_project(config, &project, None).await?
+                    }
+                    Flag::Filter(filter) => todoist::all_tasks_by_filters(config, &filter)
+                        .await?
+                        .into_iter()
+                        .flat_map(|(_, tasks)| tasks)
+                        .collect(),
+                },
+            };
+            select_search_result(config, tasks, search)
+        }
+        _ => Err(Error::new("fetch_open_task", ID_OR_SEARCH_ERR)),
+    }
+}
+
+/// Pick the task matching a search term. Only a single task containing the term is used without a prompt,
+/// so that a loose fuzzy match is never completed or deleted by mistake.
+fn select_search_result(config: &Config, tasks: Vec<Task>, search: &str) -> Result<Task, Error> {
+    let mut matches = tasks::search(tasks, search);
+    let term = search.to_lowercase();
+    let containing = matches
+        .iter()
+        .filter(|task| task.content.to_lowercase().contains(&term))
+        .count();
+    match (matches.len(), containing) {
+        (0, _) => Err(Error::new(
+            "select_search_result",
+            &format!("No tasks match '{search}'"),
+        )),
+        (1, 1) => Ok(matches.remove(0)),
+        _ => input::select(input::TASK, matches, config.mock_select),
+    }
+}
+
 fn fetch_priority(priority: &Option<u8>, config: &Config) -> Result<Priority, Error> {
     match priority::from_integer(priority) {
         Some(priority) => Ok(priority),
@@ -1628,3 +3269,303 @@ fn verify_cmd() {
     Cli::try_parse().err();
     Cli::command().debug_assert();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{self, responses::ResponseFromFile};
+    use mockito::{Matcher, Mock, ServerGuard};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_task_create_duration_requires_due() {
+        // Only the duration attribute
+        let config = test::fixtures::config().await.mock_select(5);
+        let args = TaskCreate {
+            project: None,
+            due: None,
+            description: String::new(),
+            content: None,
+            priority: None,
+            label: Vec::new(),
+            no_section: false,
+            parent: None,
+            deadline: None,
+            duration: None,
+        };
+        assert_eq!(
+            task_create(config, &args).await,
+            Err(Error::new(
+                "task_create",
+                "A duration requires a due time, select a due date as well"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_output_after_subcommand() {
+        let cli = Cli::try_parse_from(["tod", "task", "next", "--output", "json"]).unwrap();
+        assert_eq!(cli.output, OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_search_scope_requires_search() {
+        for subcommand in ["complete", "delete", "reopen"] {
+            for scope in ["--project", "--filter"] {
+                let args = ["tod", "task", subcommand, scope, "work"];
+                assert!(Cli::try_parse_from(args).is_err(), "{args:?}");
+            }
+            let args = [
+                "tod",
+                "task",
+                subcommand,
+                "--search",
+                "rent",
+                "--project",
+                "work",
+            ];
+            assert!(Cli::try_parse_from(args).is_ok(), "{args:?}");
+        }
+    }
+
+    async fn mock_comment_delete(server: &mut ServerGuard, expect: usize) -> (Mock, Mock) {
+        let get_mock = server
+            .mock("GET", "/api/v1/comments/2992679862")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Comment.read().await)
+            .create_async()
+            .await;
+        let delete_mock = server
+            .mock("DELETE", "/api/v1/comments/2992679862")
+            .with_status(204)
+            .expect(expect)
+            .create_async()
+            .await;
+        (get_mock, delete_mock)
+    }
+
+    #[tokio::test]
+    async fn test_project_comments_delete() {
+        let mut server = mockito::Server::new_async().await;
+        let (get_mock, delete_mock) = mock_comment_delete(&mut server, 1).await;
+
+        // Confirm
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(1);
+        let args = ProjectCommentsDelete {
+            project: None,
+            comment: Some("2992679862".into()),
+            force: false,
+        };
+
+        assert_eq!(
+            project_comments_delete(config, &args).await,
+            Ok(color::green_string("Comment deleted successfully"))
+        );
+        get_mock.assert();
+        delete_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_task_comments_delete_cancelled() {
+        let mut server = mockito::Server::new_async().await;
+        let (get_mock, delete_mock) = mock_comment_delete(&mut server, 0).await;
+
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(0);
+        let args = TaskCommentsDelete {
+            id: None,
+            search: None,
+            comment: Some("2992679862".into()),
+            force: false,
+        };
+
+        assert_eq!(
+            task_comments_delete(config, &args).await,
+            Ok("Cancelled".into())
+        );
+        get_mock.assert();
+        delete_mock.assert();
+    }
+
+    fn task_move_args() -> TaskMove {
+        TaskMove {
+            id: Some("6Xqhv4cwxgjwG9w8".into()),
+            search: None,
+            filter: None,
+            project: Some("myproject".into()),
+            section: None,
+            parent: None,
+        }
+    }
+
+    async fn mock_get_task(server: &mut ServerGuard, id: &str) -> Mock {
+        server
+            .mock("GET", format!("/api/v1/tasks/{id}").as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await
+    }
+
+    async fn mock_move(server: &mut ServerGuard, body: serde_json::Value) -> Mock {
+        server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8/move")
+            .match_body(Matcher::Json(body))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_task_move_to_project() {
+        let mut server = mockito::Server::new_async().await;
+        let get_mock = mock_get_task(&mut server, "6Xqhv4cwxgjwG9w8").await;
+        let move_mock = mock_move(&mut server, json!({"project_id": "123"})).await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let result = task_move(config.clone(), &task_move_args()).await;
+
+        assert_eq!(
+            result,
+            Ok(color::green_string("Moved 'TEST' to 'myproject'"))
+        );
+        get_mock.assert();
+        move_mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_task_move_to_section() {
+        let mut server = mockito::Server::new_async().await;
+        let get_mock = mock_get_task(&mut server, "6Xqhv4cwxgjwG9w8").await;
+        let sections_mock = server
+            .mock("GET", "/api/v1/sections?project_id=123&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Sections.read().await)
+            .create_async()
+            .await;
+        let move_mock = mock_move(&mut server, json!({"section_id": "1234"})).await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let args = TaskMove {
+            section: Some("Bread".into()),
+            ..task_move_args()
+        };
+        let result = task_move(config.clone(), &args).await;
+
+        assert_eq!(
+            result,
+            Ok(color::green_string("Moved 'TEST' to 'myproject'"))
+        );
+        get_mock.assert();
+        sections_mock.assert();
+        move_mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    async fn mock_get_parent(server: &mut ServerGuard, parent_id: Option<&str>) -> Mock {
+        let parent_id = serde_json::to_string(&parent_id).unwrap();
+        let body = ResponseFromFile::Task.read().await.replace(
+            "\"parent_id\": null",
+            &format!("\"parent_id\": {parent_id}"),
+        );
+        server
+            .mock("GET", "/api/v1/tasks/999999")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_task_move_to_parent() {
+        let mut server = mockito::Server::new_async().await;
+        let get_mock = mock_get_task(&mut server, "6Xqhv4cwxgjwG9w8").await;
+        let parent_mock = mock_get_parent(&mut server, None).await;
+        let move_mock = mock_move(&mut server, json!({"parent_id": "999999"})).await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let args = TaskMove {
+            project: None,
+            parent: Some("999999".into()),
+            ..task_move_args()
+        };
+        let result = task_move(config.clone(), &args).await;
+
+        assert_eq!(
+            result,
+            Ok(color::green_string(
+                "Moved 'TEST' under 'Put out recycling'"
+            ))
+        );
+        get_mock.assert();
+        parent_mock.assert();
+        move_mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_task_move_under_itself() {
+        let mut server = mockito::Server::new_async().await;
+        let get_mock = mock_get_task(&mut server, "6Xqhv4cwxgjwG9w8")
+            .await
+            .expect(2);
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let args = TaskMove {
+            project: None,
+            parent: Some("6Xqhv4cwxgjwG9w8".into()),
+            ..task_move_args()
+        };
+        let result = task_move(config, &args).await;
+
+        assert_eq!(
+            result,
+            Err(Error::new(
+                "task_move",
+                "Cannot move a task under itself or one of its subtasks"
+            ))
+        );
+        get_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_task_move_under_subtask() {
+        let mut server = mockito::Server::new_async().await;
+        // The task is fetched again while walking up from the subtask
+        let get_mock = mock_get_task(&mut server, "6Xqhv4cwxgjwG9w8")
+            .await
+            .expect(2);
+        let subtask_mock = mock_get_parent(&mut server, Some("6Xqhv4cwxgjwG9w8")).await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let args = TaskMove {
+            project: None,
+            parent: Some("999999".into()),
+            ..task_move_args()
+        };
+        let result = task_move(config, &args).await;
+
+        assert_eq!(
+            result,
+            Err(Error::new(
+                "task_move",
+                "Cannot move a task under itself or one of its subtasks"
+            ))
+        );
+        get_mock.assert();
+        subtask_mock.assert();
+    }
+}
@@ -0,0 +1,98 @@
+use serde::Serialize;
+
+use crate::{config::Config, errors::Error};
+
+#[derive(clap::ValueEnum, Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// Human readable text
+    #[default]
+    Text,
+    /// A single pretty printed JSON document
+    Json,
+    /// Newline delimited JSON, one object per line
+    Ndjson,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Ndjson => write!(f, "ndjson"),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// True when output is meant to be consumed by other programs, i.e. no spinners or colors
+    pub fn is_machine_readable(&self) -> bool {
+        *self != OutputFormat::Text
+    }
+}
+
+/// Serialize a list of items when JSON or NDJSON output was requested, returns None for text output
+pub fn maybe_list<T: Serialize>(config: &Config, items: &[T]) -> Option<Result<String, Error>> {
+    match config.args.output {
+        OutputFormat::Text => None,
+        OutputFormat::Json => Some(serde_json::to_string_pretty(items).map_err(Error::from)),
+        OutputFormat::Ndjson => Some(
+            items
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<Result<Vec<String>, _>>()
+                .map(|lines| lines.join("\n"))
+                .map_err(Error::from),
+        ),
+    }
+}
+
+/// Serialize a single item when JSON or NDJSON output was requested, returns None for text output
+pub fn maybe_single<T: Serialize>(config: &Config, item: &T) -> Option<Result<String, Error>> {
+    match config.args.output {
+        OutputFormat::Text => None,
+        OutputFormat::Json => Some(serde_json::to_string_pretty(item).map_err(Error::from)),
+        OutputFormat::Ndjson => Some(serde_json::to_string(item).map_err(Error::from)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn test_maybe_list() {
+        let config = test::fixtures::config().await;
+        let projects = vec![test::fixtures::project(), test::fixtures::project()];
+
+        assert!(maybe_list(&config, &projects).is_none());
+
+        let config = config.with_output(OutputFormat::Json);
+        let json = maybe_list(&config, &projects).unwrap().unwrap();
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0]["name"], "myproject");
+
+        let config = config.with_output(OutputFormat::Ndjson);
+        let ndjson = maybe_list(&config, &projects).unwrap().unwrap();
+        let lines: Vec<&str> = ndjson.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed["id"], "123");
+    }
+
+    #[tokio::test]
+    async fn test_maybe_single() {
+        let config = test::fixtures::config().await;
+        let task = test::fixtures::today_task().await;
+
+        assert!(maybe_single(&config, &task).is_none());
+
+        let config = config.with_output(OutputFormat::Ndjson);
+        let ndjson = maybe_single(&config, &task).unwrap().unwrap();
+        assert_eq!(ndjson.lines().count(), 1);
+        let parsed: serde_json::Value = serde_json::from_str(&ndjson).unwrap();
+        assert_eq!(parsed["content"], task.content);
+    }
+}
@@ -1,13 +1,16 @@
 use futures::future;
 use pad::PadStr;
 use std::fmt::Display;
+use std::path::Path;
+use tokio::fs;
 use tokio::task::JoinHandle;
 
 use crate::config::Config;
 use crate::errors::Error;
+use crate::export::{self, DocumentFormat};
 use crate::sections::Section;
-use crate::tasks::{FormatType, Task};
-use crate::{SortOrder, color, input, sections, tasks, todoist};
+use crate::tasks::{FormatType, NextTask, Task};
+use crate::{SortOrder, color, input, output, sections, tasks, todoist};
 use serde::{Deserialize, Serialize};
 
 const PAD_WIDTH: usize = 30;
@@ -102,6 +105,10 @@ pub async fn create(
 pub async fn list(config: &mut Config) -> Result<String, Error> {
     config.reload_projects().await?;
 
+    if let Some(result) = output::maybe_list(config, &config.projects().await?) {
+        return result;
+    }
+
     let mut project_handles = Vec::new();
 
     for project in config.projects().await? {
@@ -131,6 +138,34 @@ pub async fn list(config: &mut Config) -> Result<String, Error> {
     Ok(buffer)
 }
 
+/// Render a project's sections and tasks as a document, and write it to a file when a path is given
+pub async fn export(
+    config: &Config,
+    project: &Project,
+    format: &DocumentFormat,
+    file_path: Option<&Path>,
+) -> Result<String, Error> {
+    let (sections, tasks) = tokio::try_join!(
+        todoist::all_sections_by_project(config, project, None),
+        todoist::all_tasks_by_project(config, project, None)
+    )?;
+
+    let document = match format {
+        DocumentFormat::Markdown => export::markdown::document(config, project, &sections, &tasks)?,
+    };
+
+    match file_path {
+        None => Ok(document),
+        Some(path) => {
+            fs::write(path, document).await?;
+            Ok(color::green_string(&format!(
+                "Exported {project} to {}",
+                path.display()
+            )))
+        }
+    }
+}
+
 /// Formats a string with project name and the count that is a standard length
 async fn project_name_with_count(config: &Config, project: &Project) -> String {
     let count = match count_processable_tasks(config, project).await {
@@ -186,8 +221,15 @@ pub async fn rename(config: Config, project: &Project) -> Result<String, Error>
 pub async fn next_task(config: Config, project: &Project) -> Result<String, Error> {
     match fetch_next_task(&config, project).await {
         Ok(Some((task, remaining))) => {
-            let comments = todoist::all_comments(&config, &task, None).await?;
             config.set_next_task(task.clone()).save().await?;
+            let next = NextTask {
+                task: &task,
+                remaining,
+            };
+            if let Some(result) = output::maybe_single(&config, &next) {
+                return result;
+            }
+            let comments = todoist::all_comments(&config, &task, None).await?;
             let task_string = task
                 .fmt(comments, &config, FormatType::Single, false)
                 .await?;
@@ -539,6 +581,7 @@ pub async fn move_task_to_project(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::OutputFormat;
     use crate::test;
     use crate::test::responses::ResponseFromFile;
     use pretty_assertions::assert_eq;
@@ -626,6 +669,34 @@ mod tests {
         mock2.assert();
     }
 
+    #[tokio::test]
+    async fn test_get_next_task_json() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/tasks/?project_id=123&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTasks.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .with_output(OutputFormat::Json)
+            .create()
+            .await
+            .unwrap();
+        let project = test::fixtures::project();
+
+        let response = next_task(config, &project).await.unwrap();
+        let json: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+        assert_eq!(json["content"], "TEST");
+        assert_eq!(json["remaining"], 1);
+        mock.assert();
+    }
+
     #[tokio::test]
     async fn test_import() {
         let mut server = mockito::Server::new_async().await;
@@ -765,6 +836,7 @@ mod tests {
         mock3.assert();
         mock4.expect(2);
         mock5.expect(2);
+        crate::journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
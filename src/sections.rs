@@ -1,9 +1,9 @@
-use crate::{config::Config, errors::Error, input, projects::Project, todoist};
+use crate::{config::Config, errors::Error, input, projects::Project, tasks::Task, todoist};
 use futures::future;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 // Projects are split into sections
-#[derive(PartialEq, Deserialize, Clone, Debug)]
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug)]
 pub struct Section {
     pub id: String,
     pub name: String,
@@ -41,6 +41,21 @@ pub async fn all_sections(config: &mut Config) -> Result<Vec<Section>, Error> {
     Ok(sections)
 }
 
+/// Fetch sections only for the projects in config that the tasks belong to
+pub async fn sections_for_tasks(config: &Config, tasks: &[Task]) -> Result<Vec<Section>, Error> {
+    let projects = config.projects().await?;
+    let handles = projects
+        .iter()
+        .filter(|project| tasks.iter().any(|task| task.project_id == project.id))
+        .map(|project| todoist::all_sections_by_project(config, project, None));
+
+    future::join_all(handles)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<Vec<Section>>, Error>>()
+        .map(|sections| sections.into_iter().flatten().collect())
+}
+
 pub fn json_to_section(json: String) -> Result<Section, Error> {
     let section: Section = serde_json::from_str(&json)?;
     Ok(section)
@@ -67,6 +82,27 @@ pub async fn select_section(config: &Config, project: &Project) -> Result<Option
     }
 }
 
+/// Find a section of a project by its name
+pub async fn find_section(
+    config: &Config,
+    project: &Project,
+    name: &str,
+) -> Result<Section, Error> {
+    todoist::all_sections_by_project(config, project, None)
+        .await?
+        .into_iter()
+        .find(|section| section.name == name)
+        .ok_or_else(|| {
+            Error::new(
+                "find_section",
+                &format!(
+                    "Could not find section '{name}' in project '{}'",
+                    project.name
+                ),
+            )
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,4 +164,30 @@ mod tests {
         assert_eq!(expected, result);
         mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_find_section() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/sections?project_id=123&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Sections.read().await)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let project = test::fixtures::project();
+
+        let section = find_section(&config, &project, "Bread").await.unwrap();
+        assert_eq!(section.id, "1234");
+
+        let error = find_section(&config, &project, "Cake").await.unwrap_err();
+        assert_eq!(
+            error.message,
+            "Could not find section 'Cake' in project 'myproject'"
+        );
+        mock.assert();
+    }
 }
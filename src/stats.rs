@@ -0,0 +1,328 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{Datelike, Duration, NaiveDate};
+use chrono_tz::Tz;
+use pad::PadStr;
+
+use crate::{
+    color,
+    config::Config,
+    errors::Error,
+    projects::{Project, TaskFilter},
+    tasks::Task,
+    time, todoist,
+};
+
+const PAD_WIDTH: usize = 30;
+/// i.e. Sat 05-10
+const FORMAT_DAY: &str = "%a %m-%d";
+const DAYS_PER_WEEK: i64 = 7;
+const NO_PROJECT: &str = "Projects not in config";
+
+/// Fetch completed and open tasks and report on how things are going
+pub async fn report(config: &Config, weeks: u8) -> Result<String, Error> {
+    if weeks == 0 {
+        return Err(Error::new("stats", "Weeks must be at least 1"));
+    }
+    let today = time::naive_date_today(config)?;
+    let (since, _) = window(today, weeks);
+
+    let (completed, open) = tokio::try_join!(
+        todoist::all_completed_tasks(config, since, today, None, None),
+        todoist::all_tasks(config)
+    )?;
+
+    let projects = config.projects().await?;
+    render(config, &completed, &open, &projects, today, weeks)
+}
+
+fn render(
+    config: &Config,
+    completed: &[Task],
+    open: &[Task],
+    projects: &[Project],
+    today: NaiveDate,
+    weeks: u8,
+) -> Result<String, Error> {
+    let tz = time::timezone_from_str(&config.get_timezone()?)?;
+    let completed_dates = completion_dates(completed, tz);
+    let (since, first_monday) = window(today, weeks);
+
+    let mut buffer = String::new();
+
+    buffer.push_str(&heading("Completed per day"));
+    for offset in (0..DAYS_PER_WEEK).rev() {
+        let day = today - Duration::days(offset);
+        let count = completed_dates.iter().filter(|date| **date == day).count();
+        buffer.push_str(&row(&day.format(FORMAT_DAY).to_string(), count));
+    }
+
+    buffer.push_str(&heading("Completed per week"));
+    let mut monday = first_monday;
+    while monday <= today {
+        let next_monday = monday + Duration::days(DAYS_PER_WEEK);
+        let count = completed_dates
+            .iter()
+            .filter(|date| **date >= monday && **date < next_monday)
+            .count();
+        let label = format!("Week of {}", monday.format(time::FORMAT_DATE));
+        buffer.push_str(&row(&label, count));
+        monday = next_monday;
+    }
+
+    let (current, longest) = streaks(&completed_dates, since, today);
+    buffer.push_str(&heading("Streaks"));
+    buffer.push_str(&row("Current (days)", current));
+    buffer.push_str(&row(&format!("Longest in {weeks} week(s)"), longest));
+
+    let without_due = open.iter().filter(|task| task.due.is_none()).count();
+    buffer.push_str(&heading("Open tasks"));
+    buffer.push_str(&row("Total", open.len()));
+    buffer.push_str(&row("Without due date", without_due));
+    if let Some(average) = average_age(config, open, tz)? {
+        let label = "Average age (days)".pad_to_width(PAD_WIDTH);
+        buffer.push_str(&format!("  {label}{average:.1}\n"));
+    }
+
+    let mut overdue: BTreeMap<String, usize> = BTreeMap::new();
+    for task in open
+        .iter()
+        .filter(|task| task.filter(config, &TaskFilter::Overdue))
+    {
+        let name = projects
+            .iter()
+            .find(|project| project.id == task.project_id)
+            .map(|project| project.name.clone())
+            .unwrap_or_else(|| NO_PROJECT.into());
+        *overdue.entry(name).or_default() += 1;
+    }
+    buffer.push_str(&heading("Overdue by project"));
+    if overdue.is_empty() {
+        buffer.push_str("  None\n");
+    }
+    for (name, count) in overdue {
+        buffer.push_str(&row(&name, count));
+    }
+
+    Ok(buffer)
+}
+
+/// Returns the first day to fetch completions for, and the Monday that starts the first of the calendar weeks.
+/// The window covers the current week and the weeks before it, and always includes the last 7 days.
+fn window(today: NaiveDate, weeks: u8) -> (NaiveDate, NaiveDate) {
+    let this_monday = today - Duration::days(today.weekday().num_days_from_monday().into());
+    let first_monday = this_monday - Duration::days((i64::from(weeks) - 1) * DAYS_PER_WEEK);
+    let since = first_monday.min(today - Duration::days(DAYS_PER_WEEK - 1));
+    (since, first_monday)
+}
+
+/// The local date each task was completed on
+fn completion_dates(completed: &[Task], tz: Tz) -> Vec<NaiveDate> {
+    completed
+        .iter()
+        .filter_map(|task| task.completed_at.as_ref())
+        .filter_map(|completed_at| time::datetime_from_str(completed_at, tz).ok())
+        .map(|datetime| datetime.with_timezone(&tz).date_naive())
+        .collect()
+}
+
+/// Consecutive days with at least one completion. The current streak is still alive if nothing has been completed yet today.
+fn streaks(dates: &[NaiveDate], since: NaiveDate, today: NaiveDate) -> (usize, usize) {
+    let days: HashSet<&NaiveDate> = dates.iter().collect();
+
+    let mut current = 0;
+    let mut day = if days.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+    while day >= since && days.contains(&day) {
+        current += 1;
+        day -= Duration::days(1);
+    }
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut day = since;
+    while day <= today {
+        if days.contains(&day) {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+        day += Duration::days(1);
+    }
+
+    (current, longest)
+}
+
+/// Average number of days since open tasks were added
+fn average_age(config: &Config, open: &[Task], tz: Tz) -> Result<Option<f64>, Error> {
+    let now = time::datetime_now(config)?;
+    let ages = open
+        .iter()
+        .filter_map(|task| task.added_at.as_ref())
+        .filter_map(|added_at| time::datetime_from_str(added_at, tz).ok())
+        .map(|added_at| (now - added_at.with_timezone(&now.timezone())).num_hours() as f64 / 24.0)
+        .collect::<Vec<f64>>();
+
+    if ages.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(ages.iter().sum::<f64>() / ages.len() as f64))
+    }
+}
+
+fn heading(text: &str) -> String {
+    format!("\n{}\n", color::green_string(text))
+}
+
+fn row(label: &str, count: usize) -> String {
+    format!("  {}{count}\n", label.pad_to_width(PAD_WIDTH))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::DateInfo;
+    use crate::test;
+    use crate::test::responses::ResponseFromFile;
+    use pretty_assertions::assert_eq;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 5, day).unwrap()
+    }
+
+    #[test]
+    fn test_window() {
+        // Saturday
+        assert_eq!(window(date(10), 1), (date(4), date(5)));
+        assert_eq!(
+            window(date(10), 2),
+            (
+                NaiveDate::from_ymd_opt(2025, 4, 28).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 4, 28).unwrap()
+            )
+        );
+        // Sunday
+        assert_eq!(window(date(11), 1), (date(5), date(5)));
+    }
+
+    #[test]
+    fn test_streaks() {
+        let since = date(1);
+        let today = date(10);
+
+        assert_eq!(streaks(&[], since, today), (0, 0));
+        // Nothing done yet today, but yesterday keeps the streak going
+        assert_eq!(
+            streaks(&[date(2), date(3), date(4), date(8), date(9)], since, today),
+            (2, 3)
+        );
+        assert_eq!(
+            streaks(&[date(9), date(10), date(10)], since, today),
+            (2, 2)
+        );
+        assert_eq!(streaks(&[date(8)], since, today), (0, 1));
+    }
+
+    #[tokio::test]
+    async fn test_report() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock(
+                "GET",
+                "/api/v1/tasks/completed/by_completion_date?since=2025-05-04T07%3A00%3A00Z&until=2025-05-11T07%3A00%3A00Z&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::CompletedTasks.read().await)
+            .create_async()
+            .await;
+        let mock2 = server
+            .mock("GET", "/api/v1/tasks/?limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTasks.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let result = report(&config, 1).await.unwrap();
+
+        assert!(result.contains("Completed per week"));
+        assert!(result.contains(&format!("{}2", "Sat 05-10".pad_to_width(PAD_WIDTH))));
+        assert!(result.contains(&format!("{}2", "Current (days)".pad_to_width(PAD_WIDTH))));
+        assert!(result.contains(&format!("{}1", "Total".pad_to_width(PAD_WIDTH))));
+        mock.assert();
+        mock2.assert();
+    }
+
+    #[tokio::test]
+    async fn test_render() {
+        let config = test::fixtures::config().await;
+        let project = test::fixtures::project();
+        let base = test::fixtures::today_task().await;
+        let overdue = Task {
+            project_id: project.id.clone(),
+            added_at: Some("2025-05-05T10:00:00Z".into()),
+            due: Some(DateInfo {
+                date: "2025-05-01".into(),
+                is_recurring: false,
+                string: String::new(),
+                lang: "en".into(),
+                timezone: None,
+            }),
+            ..base.clone()
+        };
+        let undated = Task {
+            due: None,
+            added_at: Some("2025-05-09T10:00:00Z".into()),
+            ..base.clone()
+        };
+        let completed = vec![Task {
+            completed_at: Some("2025-05-06T18:00:00Z".into()),
+            ..base.clone()
+        }];
+
+        let result = render(
+            &config,
+            &completed,
+            &[overdue, undated],
+            &[project],
+            date(10),
+            2,
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            "\n\
+            Completed per day\n  \
+              Sun 05-04                     0\n  \
+              Mon 05-05                     0\n  \
+              Tue 05-06                     1\n  \
+              Wed 05-07                     0\n  \
+              Thu 05-08                     0\n  \
+              Fri 05-09                     0\n  \
+              Sat 05-10                     0\n\
+            \n\
+            Completed per week\n  \
+              Week of 2025-04-28            0\n  \
+              Week of 2025-05-05            1\n\
+            \n\
+            Streaks\n  \
+              Current (days)                0\n  \
+              Longest in 2 week(s)          1\n\
+            \n\
+            Open tasks\n  \
+              Total                         2\n  \
+              Without due date              1\n  \
+              Average age (days)            3.0\n\
+            \n\
+            Overdue by project\n  \
+              myproject                     1\n"
+        );
+    }
+}
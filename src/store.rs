@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::fs;
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::{config::Config, errors::Error};
+
+/// A list kept in a JSON file next to the config, i.e. $XDG_CONFIG_HOME/tod.journal.
+/// Only the most recent entries are kept.
+pub struct Store {
+    extension: &'static str,
+    max_entries: usize,
+    /// Entries are added from several threads at once, i.e. in list process
+    lock: Mutex<()>,
+}
+
+impl Store {
+    pub const fn new(extension: &'static str, max_entries: usize) -> Store {
+        Store {
+            extension,
+            max_entries,
+            lock: Mutex::const_new(()),
+        }
+    }
+
+    /// Hold this while loading, changing and saving entries
+    pub async fn lock(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+
+    fn path(&self, config: &Config) -> PathBuf {
+        let path = config.path.with_extension(self.extension);
+        match path.file_name() {
+            // Every test config has its own name, so tests get their own files outside of the repository
+            Some(name) if cfg!(test) => std::env::temp_dir().join(name),
+            _ => path,
+        }
+    }
+
+    /// Add an entry, dropping the oldest ones once there are too many
+    pub async fn push<T: Serialize + DeserializeOwned>(
+        &self,
+        config: &Config,
+        entry: T,
+    ) -> Result<(), Error> {
+        let _lock = self.lock().await;
+        let mut entries = self.load(config).await?;
+        entries.push(entry);
+        self.save(config, &entries).await
+    }
+
+    /// Oldest first
+    pub async fn load<T: DeserializeOwned>(&self, config: &Config) -> Result<Vec<T>, Error> {
+        match fs::read_to_string(self.path(config)).await {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn save<T: Serialize>(&self, config: &Config, entries: &[T]) -> Result<(), Error> {
+        let excess = entries.len().saturating_sub(self.max_entries);
+        let json = serde_json::to_string_pretty(&entries[excess..])?;
+        fs::write(self.path(config), json).await?;
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub async fn remove(&self, config: &Config) {
+        fs::remove_file(self.path(config)).await.unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+    use pretty_assertions::assert_eq;
+
+    static STORE: Store = Store::new("teststore", 2);
+
+    #[tokio::test]
+    async fn test_push_and_load() {
+        let config = test::fixtures::config().await;
+        assert_eq!(STORE.load::<u8>(&config).await, Ok(Vec::new()));
+
+        for entry in 1..=3 {
+            STORE.push(&config, entry).await.unwrap();
+        }
+        // Only the most recent entries are kept
+        assert_eq!(STORE.load::<u8>(&config).await, Ok(vec![2, 3]));
+        STORE.remove(&config).await;
+    }
+}
@@ -10,17 +10,19 @@ use tokio::task::JoinHandle;
 
 pub mod format;
 pub mod priority;
-use crate::comments::Comment;
+use crate::comments::{Attachment, Comment};
 use crate::config::Config;
 use crate::config::SortValue;
 use crate::debug;
 use crate::errors::Error;
 use crate::input::CONTENT;
-use crate::input::DateTimeInput;
+use crate::input::{DateTimeInput, Snooze};
+use crate::output;
 use crate::projects;
+use crate::sections::Section;
 use crate::tasks;
 use crate::tasks::priority::Priority;
-use crate::{input, time, todoist};
+use crate::{color, input, labels, sections, time, todoist};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub struct Task {
@@ -51,12 +53,37 @@ pub struct Task {
     pub day_order: i16,
 }
 
+/// Everything shown by `task show`, with the fields of the task at the top level
+#[derive(Serialize)]
+struct TaskDetails<'a> {
+    #[serde(flatten)]
+    task: &'a Task,
+    section: Option<&'a Section>,
+    subtasks: &'a [Task],
+    comments: &'a [Comment],
+}
+
+/// The task given by `task next`, with the number of tasks left like in text output
+#[derive(Serialize)]
+pub struct NextTask<'a> {
+    #[serde(flatten)]
+    pub task: &'a Task,
+    pub remaining: usize,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub struct TaskResponse {
     pub results: Vec<Task>,
     pub next_cursor: Option<String>,
 }
 
+/// The completed tasks endpoints use items instead of results
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct CompletedTaskResponse {
+    pub items: Vec<Task>,
+    pub next_cursor: Option<String>,
+}
+
 // Update task_attributes fn when adding here
 #[derive(Eq, PartialEq)]
 pub enum TaskAttribute {
@@ -66,6 +93,8 @@ pub enum TaskAttribute {
     Due,
     Labels,
     Deadline,
+    Duration,
+    Parent,
 }
 impl Display for TaskAttribute {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -76,6 +105,8 @@ impl Display for TaskAttribute {
             TaskAttribute::Due => write!(f, "Due"),
             TaskAttribute::Labels => write!(f, "Labels"),
             TaskAttribute::Deadline => write!(f, "Deadline"),
+            TaskAttribute::Duration => write!(f, "Duration"),
+            TaskAttribute::Parent => write!(f, "Parent"),
         }
     }
 }
@@ -99,6 +130,8 @@ pub fn create_task_attributes() -> Vec<TaskAttribute> {
         TaskAttribute::Due,
         TaskAttribute::Labels,
         TaskAttribute::Deadline,
+        TaskAttribute::Duration,
+        TaskAttribute::Parent,
     ]
 }
 
@@ -141,10 +174,12 @@ struct Body {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
 pub enum Unit {
-    #[serde(rename(deserialize = "minute"))]
+    // Older versions saved tasks with capitalized units, i.e. next_taskv1 in the config
+    #[serde(alias = "Minute")]
     Minute,
-    #[serde(rename(deserialize = "day"))]
+    #[serde(alias = "Day")]
     Day,
 }
 
@@ -153,7 +188,7 @@ pub enum FormatType {
     Single,
 }
 
-enum DateTimeInfo {
+pub enum DateTimeInfo {
     NoDateTime,
     Date {
         date: NaiveDate,
@@ -369,8 +404,16 @@ impl Task {
         }
     }
 
+    /// Durations can only be set on tasks that are due at a time of day
+    pub fn has_due_time(&self, config: &Config) -> Result<bool, Error> {
+        Ok(matches!(
+            self.datetimeinfo(config)?,
+            DateTimeInfo::DateTime { .. }
+        ))
+    }
+
     /// Converts the JSON date representation into Date or Datetime
-    fn datetimeinfo(&self, config: &Config) -> Result<DateTimeInfo, Error> {
+    pub fn datetimeinfo(&self, config: &Config) -> Result<DateTimeInfo, Error> {
         let tz_string = config.get_timezone()?;
         let tz = match self.clone().due {
             None => time::timezone_from_str(&tz_string)?,
@@ -507,6 +550,22 @@ pub async fn update_task(
         }
         TaskAttribute::Due => tasks::spawn_schedule_task(config.clone(), task.clone()).await,
         TaskAttribute::Deadline => tasks::spawn_deadline_task(config.clone(), task.clone()).await,
+        TaskAttribute::Duration => {
+            if !task.has_due_time(config)? {
+                return Err(Error::new(
+                    "update_task",
+                    "A duration requires a due time, schedule the task first",
+                ));
+            }
+            let duration = input::string(input::DURATION, config.mock_string.clone())?;
+            let handle =
+                spawn_update_task_duration(config.clone(), task.clone(), duration.parse::<u32>()?);
+            Ok(Some(handle))
+        }
+        TaskAttribute::Parent => Err(Error::new(
+            "update_task",
+            "The parent of an existing task cannot be changed",
+        )),
         TaskAttribute::Labels => {
             let label_string = input::string(
                 "Enter labels separated by spaces:",
@@ -524,6 +583,188 @@ pub async fn update_task(
     }
 }
 
+/// Fetch a task along with its section, subtasks and comments, and show all of its details
+pub async fn show(config: &Config, id: &str) -> Result<String, Error> {
+    let task = todoist::get_task(config, id).await?;
+    let (comments, mut subtasks, completed) = tokio::try_join!(
+        todoist::all_comments(config, &task, None),
+        todoist::all_subtasks(config, &task),
+        todoist::all_completed_subtasks(config, &task)
+    )?;
+    // Completed subtasks are shown as done along with the open ones
+    subtasks.extend(completed);
+    let section = match &task.section_id {
+        Some(section_id) => Some(todoist::get_section(config, section_id).await?),
+        None => None,
+    };
+
+    let details = TaskDetails {
+        task: &task,
+        section: section.as_ref(),
+        subtasks: &subtasks,
+        comments: &comments,
+    };
+    if let Some(result) = output::maybe_single(config, &details) {
+        return result;
+    }
+
+    render_details(config, &task, section.as_ref(), &subtasks, comments).await
+}
+
+async fn render_details(
+    config: &Config,
+    task: &Task,
+    section: Option<&Section>,
+    subtasks: &[Task],
+    comments: Vec<Comment>,
+) -> Result<String, Error> {
+    let text = task
+        .fmt(Vec::new(), config, FormatType::Single, true)
+        .await?;
+    let mut buffer = format!("{}\n", text.trim_end());
+
+    if let Some(section) = section {
+        buffer.push_str(&format!("Section: {}\n", section.name));
+    }
+    if let Some(deadline) = &task.deadline {
+        buffer.push_str(&format!("Deadline: {}\n", deadline.date));
+    }
+    if let Some(duration) = &task.duration {
+        buffer.push_str(&format!("Duration: {}\n", format::duration(duration)));
+    }
+
+    if !subtasks.is_empty() {
+        let mut subtasks = subtasks.iter().collect::<Vec<&Task>>();
+        subtasks.sort_by_key(|subtask| subtask.child_order);
+        buffer.push_str(&format!("\n{}\n", color::green_string("Subtasks")));
+        for subtask in subtasks {
+            let checkbox = if subtask.checked { "[x]" } else { "[ ]" };
+            buffer.push_str(&format!(
+                "- {checkbox} {}\n",
+                format::content(subtask, config)
+            ));
+        }
+    }
+
+    let attachments = comments
+        .iter()
+        .filter_map(|comment| comment.file_attachment.as_ref())
+        .collect::<Vec<&Attachment>>();
+    if !attachments.is_empty() {
+        buffer.push_str(&format!("\n{}\n", color::green_string("Attachments")));
+        for attachment in attachments {
+            buffer.push_str(&format!(
+                "- {} {}\n",
+                attachment.label(),
+                format::maybe_format_url(attachment.url(), config)
+            ));
+        }
+    }
+
+    if !comments.is_empty() {
+        let count = comments.len();
+        let rendered = format::render_comments(config, comments).await?;
+        // Comments longer than the maximum length are cut short
+        let truncated = rendered.len() > config.max_comment_length().try_into()?;
+        buffer.push_str(&rendered);
+        buffer.push('\n');
+        if truncated {
+            buffer.push_str(&format!(
+                "\nShowing part of {count} comment(s), use --output json to see all of them\n"
+            ));
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Recreate a task and its subtasks in a project, optionally copying comments too. Returns the new tasks, parents first.
+pub async fn duplicate(
+    config: &Config,
+    task: &Task,
+    project: &projects::Project,
+    with_comments: bool,
+) -> Result<Vec<Task>, Error> {
+    // Sections belong to a project, so they only carry over within the same project
+    let section = match &task.section_id {
+        Some(section_id) if task.project_id == project.id => {
+            Some(todoist::get_section(config, section_id).await?)
+        }
+        _ => None,
+    };
+
+    let mut created = Vec::new();
+    // Depth first so that every parent exists before its subtasks
+    let mut stack = vec![(task.clone(), None)];
+    while let Some((original, parent_id)) = stack.pop() {
+        // Subtasks are placed in the section of their parent
+        let section = if parent_id.is_none() {
+            section.clone()
+        } else {
+            None
+        };
+        let new_task = duplicate_one(config, &original, project, section, parent_id).await?;
+        if with_comments {
+            let comments = todoist::all_comments(config, &original, None).await?;
+            copy_comments(config, comments, &new_task).await?;
+        }
+
+        let mut subtasks = todoist::all_subtasks(config, &original)
+            .await?
+            .into_iter()
+            .filter(|subtask| subtask.parent_id.as_ref() == Some(&original.id))
+            .collect::<Vec<Task>>();
+        subtasks.sort_by_key(|subtask| Reverse(subtask.child_order));
+        for subtask in subtasks {
+            stack.push((subtask, Some(new_task.id.clone())));
+        }
+        created.push(new_task);
+    }
+    Ok(created)
+}
+
+async fn duplicate_one(
+    config: &Config,
+    task: &Task,
+    project: &projects::Project,
+    section: Option<Section>,
+    parent_id: Option<String>,
+) -> Result<Task, Error> {
+    let copy = Task {
+        project_id: project.id.clone(),
+        section_id: section.map(|section| section.id),
+        parent_id,
+        ..task.clone()
+    };
+    todoist::recreate_task(config, &copy).await
+}
+
+/// Add comments to a task, keeping their attachments
+pub async fn copy_comments(
+    config: &Config,
+    comments: Vec<Comment>,
+    task: &Task,
+) -> Result<(), Error> {
+    for comment in comments {
+        match comment.file_attachment {
+            Some(attachment) => {
+                todoist::create_comment_with_attachment(
+                    config,
+                    task,
+                    comment.content,
+                    &attachment,
+                    false,
+                )
+                .await?;
+            }
+            _ => {
+                todoist::create_comment(config, task, comment.content, false).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
 pub async fn label_task(
     config: &Config,
     task: Task,
@@ -552,17 +793,25 @@ pub async fn process_task(
     task_count: &mut i32,
     with_project: bool,
 ) -> Result<Option<JoinHandle<()>>, Error> {
-    let options = [
+    let mut options = vec![
         input::COMPLETE,
         input::SKIP,
         input::SCHEDULE,
-        input::COMMENT,
-        input::DELETE,
-        input::QUIT,
-    ]
-    .iter()
-    .map(|s| s.to_string())
-    .collect();
+        input::SNOOZE_TOMORROW,
+        input::SNOOZE_WEEKDAY,
+        input::SNOOZE_WEEK,
+    ];
+    // Recurring tasks keep their time of day when postponed, so only whole days make sense
+    if task.is_recurring() {
+        options.extend([input::POSTPONE, input::SKIP_OCCURRENCE]);
+    } else {
+        options.push(input::SNOOZE_HOURS);
+    }
+    let options = options
+        .into_iter()
+        .chain([input::COMMENT, input::DELETE, input::QUIT])
+        .map(|s| s.to_string())
+        .collect();
     let formatted_task = task
         .fmt(comments, config, FormatType::Single, with_project)
         .await?;
@@ -592,6 +841,28 @@ pub async fn process_task(
                 None,
             )))
         }
+        input::SNOOZE_TOMORROW
+        | input::SNOOZE_WEEKDAY
+        | input::SNOOZE_WEEK
+        | input::SNOOZE_HOURS => {
+            let snooze = match selection.as_str() {
+                input::SNOOZE_TOMORROW => Snooze::Tomorrow,
+                input::SNOOZE_WEEKDAY => Snooze::Weekday,
+                input::SNOOZE_WEEK => Snooze::Week,
+                _ => input::snooze_hours(config.mock_string.clone())?,
+            };
+            let due = snooze_due(config, &task, snooze)?;
+            if task.is_recurring() {
+                Ok(Some(spawn_postpone_occurrence(config.clone(), task, due)))
+            } else {
+                Ok(Some(spawn_update_task_due(config.clone(), task, due, None)))
+            }
+        }
+        input::POSTPONE => {
+            let date = input::date()?;
+            Ok(Some(spawn_postpone_occurrence(config.clone(), task, date)))
+        }
+        input::SKIP_OCCURRENCE => Ok(Some(spawn_skip_occurrence(config.clone(), task))),
         input::SKIP => {
             // Do nothing
             Ok(Some(tokio::spawn(async move {})))
@@ -603,6 +874,163 @@ pub async fn process_task(
     }
 }
 
+/// The new due date for a snooze, i.e. "2025-05-11" or "2025-05-11 15:00".
+/// Days keep the time of day of tasks that have one, and recurring tasks only get a date.
+fn snooze_due(config: &Config, task: &Task, snooze: Snooze) -> Result<String, Error> {
+    let today = time::naive_date_today(config)?;
+    let date = match snooze {
+        Snooze::Hours(hours) => {
+            let datetime = time::datetime_now(config)? + chrono::Duration::hours(hours);
+            return Ok(datetime.format(time::FORMAT_DATE_AND_TIME).to_string());
+        }
+        Snooze::Tomorrow => today + chrono::Duration::days(1),
+        Snooze::Weekday => time::next_weekday(today),
+        Snooze::Week => time::next_monday(today),
+    };
+    let date = date.format(time::FORMAT_DATE).to_string();
+    match task.datetimeinfo(config)? {
+        DateTimeInfo::DateTime { datetime, .. } if !task.is_recurring() => {
+            let tz = time::timezone_from_str(&config.get_timezone()?)?;
+            let time = datetime.with_timezone(&tz).format(time::FORMAT_TIME);
+            Ok(format!("{date} {time}"))
+        }
+        _ => Ok(date),
+    }
+}
+
+/// Offers every action for a task in one menu, and keeps offering them until the task is finished with.
+/// Returns false when the user quits.
+pub async fn triage_task(
+    config: &Config,
+    mut task: Task,
+    task_count: &mut i32,
+    with_project: bool,
+    handles: &mut Vec<JoinHandle<()>>,
+) -> Result<bool, Error> {
+    let formatted_task = task
+        .fmt(Vec::new(), config, FormatType::Single, with_project)
+        .await?;
+    println!("{formatted_task}{task_count} task(s) remaining");
+    *task_count -= 1;
+
+    loop {
+        let options = vec![
+            input::SKIP,
+            input::COMPLETE,
+            input::SCHEDULE,
+            input::TIMEBOX,
+            input::PRIORITIZE,
+            input::LABEL,
+            input::DEADLINE,
+            input::MOVE,
+            input::EDIT,
+            input::COMMENT,
+            input::DELETE,
+            input::QUIT,
+        ];
+        match input::select(input::OPTION, options, config.mock_select)? {
+            input::SKIP => return Ok(true),
+            input::QUIT => return Ok(false),
+            input::COMPLETE => {
+                handles.push(spawn_complete_task(config.clone(), task));
+                return Ok(true);
+            }
+            input::DELETE => {
+                handles.push(spawn_delete_task(config.clone(), task));
+                return Ok(true);
+            }
+            action => {
+                if let Some(handle) = triage_action(config, &task, action).await? {
+                    // Finish each change and fetch the task again, so that the next action starts from it
+                    handle.await?;
+                    task = todoist::get_task(config, &task.id).await?;
+                    if task.checked {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Carries out one of the triage actions that leave the task open
+async fn triage_action(
+    config: &Config,
+    task: &Task,
+    action: &str,
+) -> Result<Option<JoinHandle<()>>, Error> {
+    match action {
+        input::SCHEDULE => update_task(config, task, &TaskAttribute::Due).await,
+        // The time of a recurring task comes from its due string, so only the duration is set
+        input::TIMEBOX if task.is_recurring() && task.has_due_time(config)? => {
+            update_task(config, task, &TaskAttribute::Duration).await
+        }
+        // Giving a recurring task a time would replace its schedule, so ask what is meant first
+        input::TIMEBOX if task.is_recurring() => {
+            println!(
+                "A recurring task needs a due time before it can be timeboxed. Schedule it with a time, then choose {} again.",
+                input::TIMEBOX
+            );
+            update_task(config, task, &TaskAttribute::Due).await
+        }
+        input::TIMEBOX => {
+            let (due_string, duration) = get_timebox(config, task)?;
+            Ok(Some(spawn_update_task_due(
+                config.clone(),
+                task.clone(),
+                due_string,
+                Some(duration),
+            )))
+        }
+        input::PRIORITIZE => update_task(config, task, &TaskAttribute::Priority).await,
+        input::LABEL => {
+            let all_labels = labels::get_labels(config, false).await?;
+            let selected = input::multi_select(input::LABELS, all_labels, config.mock_select)?;
+            // Add to the labels already on the task, like the label command
+            let mut labels = task.labels.clone();
+            for label in selected {
+                if !labels.contains(&label.name) {
+                    labels.push(label.name);
+                }
+            }
+            if labels == task.labels {
+                return Ok(None);
+            }
+            Ok(Some(spawn_update_task_labels(
+                config.clone(),
+                task.clone(),
+                labels,
+            )))
+        }
+        input::DEADLINE => update_task(config, task, &TaskAttribute::Deadline).await,
+        input::MOVE => {
+            let projects = config.projects().await?;
+            let project = input::select(input::PROJECT, projects, config.mock_select)?;
+            let section = if config.no_sections.unwrap_or_default() {
+                None
+            } else {
+                sections::select_section(config, &project).await?
+            };
+            Ok(Some(spawn_move_task(
+                config.clone(),
+                task.clone(),
+                project,
+                section,
+            )))
+        }
+        input::EDIT => update_task(config, task, &TaskAttribute::Content).await,
+        input::COMMENT => {
+            let content = input::string(CONTENT, config.mock_string.clone())?;
+            Ok(Some(spawn_comment_task(
+                config.clone(),
+                task.clone(),
+                content,
+            )))
+        }
+        _ => unreachable!(),
+    }
+}
+
 pub async fn timebox_task(
     config: &Config,
     task: Task,
@@ -694,6 +1122,26 @@ pub async fn spawn_schedule_task(
         .fmt(comments, &config, FormatType::Single, true)
         .await?;
     println!("{text}");
+    if task.is_recurring() {
+        // Changing the due string of a recurring task changes its schedule, so ask what is meant first
+        let options = vec![
+            input::POSTPONE,
+            input::SKIP_OCCURRENCE,
+            input::RESCHEDULE,
+            input::SKIP,
+            input::COMPLETE,
+        ];
+        match input::select(input::OPTION, options, config.mock_select)? {
+            input::POSTPONE => {
+                let date = input::date()?;
+                return Ok(Some(spawn_postpone_occurrence(config, task, date)));
+            }
+            input::SKIP_OCCURRENCE => return Ok(Some(spawn_skip_occurrence(config, task))),
+            input::SKIP => return Ok(None),
+            input::COMPLETE => return Ok(Some(spawn_complete_task(config, task))),
+            _ => (),
+        }
+    }
     let datetime_input = input::datetime(
         config.mock_select,
         config.mock_string.clone(),
@@ -777,6 +1225,42 @@ pub fn spawn_delete_task(config: Config, task: Task) -> JoinHandle<()> {
     })
 }
 
+/// Moves task, and its subtasks, to a project or one of its sections inside another thread
+pub fn spawn_move_task(
+    config: Config,
+    task: Task,
+    project: projects::Project,
+    section: Option<Section>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let result = match section {
+            Some(section) => todoist::move_task_to_section(&config, &task, &section, false).await,
+            None => todoist::move_task_to_project(&config, &task, &project, false).await,
+        };
+        if let Err(e) = result {
+            config.tx().send(e).unwrap();
+        }
+    })
+}
+
+/// Postpones the current occurrence of a recurring task inside another thread
+pub fn spawn_postpone_occurrence(config: Config, task: Task, date: String) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(e) = todoist::postpone_occurrence(&config, &task, &date, false).await {
+            config.tx().send(e).unwrap();
+        }
+    })
+}
+
+/// Moves a recurring task to its next occurrence inside another thread
+pub fn spawn_skip_occurrence(config: Config, task: Task) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(e) = todoist::skip_occurrence(&config, &task, false).await {
+            config.tx().send(e).unwrap();
+        }
+    })
+}
+
 /// Updates task inside another thread
 pub fn spawn_update_task_due(
     config: Config,
@@ -847,6 +1331,15 @@ pub fn spawn_update_task_labels(config: Config, task: Task, labels: Vec<String>)
     })
 }
 
+/// Updates task inside another thread
+pub fn spawn_update_task_duration(config: Config, task: Task, duration: u32) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(e) = todoist::update_task_duration(&config, &task, duration, false).await {
+            config.tx().send(e).unwrap();
+        }
+    })
+}
+
 /// Updates task inside another thread
 pub fn spawn_update_task_priority(
     config: Config,
@@ -871,6 +1364,11 @@ pub fn json_to_tasks_response(json: String) -> Result<TaskResponse, Error> {
     Ok(response)
 }
 
+pub fn json_to_completed_tasks_response(json: String) -> Result<CompletedTaskResponse, Error> {
+    let response: CompletedTaskResponse = serde_json::from_str(&json)?;
+    Ok(response)
+}
+
 pub fn sort_by_value(mut tasks: Vec<Task>, config: &Config) -> Vec<Task> {
     tasks.sort_by_key(|b| Reverse(b.value(config)));
     tasks
@@ -935,6 +1433,62 @@ pub async fn reject_parent_tasks(tasks: Vec<Task>, config: &Config) -> Vec<Task>
         .collect::<Vec<Task>>()
 }
 
+/// Tasks without a parent, or whose parent is not in the list, in their existing order
+pub fn top_level_tasks(tasks: &[Task]) -> Vec<&Task> {
+    tasks
+        .iter()
+        .filter(|task| {
+            task.parent_id
+                .as_ref()
+                .is_none_or(|parent_id| !tasks.iter().any(|t| &t.id == parent_id))
+        })
+        .collect()
+}
+
+/// Direct subtasks of a task that are in the list, ordered by child_order
+pub fn subtasks<'a>(parent: &Task, tasks: &'a [Task]) -> Vec<&'a Task> {
+    let mut subtasks = tasks
+        .iter()
+        .filter(|task| task.parent_id.as_ref() == Some(&parent.id))
+        .collect::<Vec<&Task>>();
+    subtasks.sort_by_key(|task| task.child_order);
+    subtasks
+}
+
+/// Tasks whose content contains the characters of the search term in order, ignoring case.
+/// Tasks containing the term as a whole come first, then those where the matched characters are closest together.
+pub fn search(tasks: Vec<Task>, term: &str) -> Vec<Task> {
+    let term = term.to_lowercase();
+    let mut matches = tasks
+        .into_iter()
+        .filter_map(|task| {
+            let content = task.content.to_lowercase();
+            if content.contains(&term) {
+                return Some((0, task));
+            }
+            match_span(&content, &term).map(|span| (span, task))
+        })
+        .collect::<Vec<(usize, Task)>>();
+    matches.sort_by_key(|(span, _)| *span);
+    matches.into_iter().map(|(_, task)| task).collect()
+}
+
+/// Number of characters between the first and last matched character, if all of the term is found in order
+fn match_span(content: &str, term: &str) -> Option<usize> {
+    let mut chars = term.chars().peekable();
+    let mut first = None;
+    for (index, char) in content.chars().enumerate() {
+        if chars.peek() == Some(&char) {
+            chars.next();
+            let first = *first.get_or_insert(index);
+            if chars.peek().is_none() {
+                return Some(index - first + 1);
+            }
+        }
+    }
+    None
+}
+
 // Need to make sure that we are not completing a subtask for a parent task that is in the future
 async fn parent_in_future(task: Task, tasks: Vec<Task>, config: &Config) -> bool {
     let task_ids: Vec<String> = tasks.clone().into_iter().map(|task| task.id).collect();
@@ -1402,6 +1956,7 @@ mod tests {
 
         tokio::join!(future).0.unwrap();
         mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
@@ -1433,40 +1988,465 @@ mod tests {
             .await
             .unwrap();
         mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
-    async fn test_display_task() {
-        let task = test::fixtures::today_task().await;
-        let string = String::from("TEST");
-        assert_eq!(string, task.to_string())
-    }
-    #[tokio::test]
-    async fn test_deadline_value_when_today() {
+    async fn test_snooze_due() {
         let config = test::fixtures::config().await;
         let task = test::fixtures::today_task().await;
-        let value = task.deadline_value(&config).unwrap();
-        assert_eq!(value, 150);
+        let recurring = Task {
+            due: Some(DateInfo {
+                is_recurring: true,
+                ..task.due.clone().unwrap()
+            }),
+            ..task.clone()
+        };
+
+        // Saturday at 3am
+        assert_eq!(
+            snooze_due(&config, &task, Snooze::Hours(1)),
+            Ok("2025-05-10 04:00".into())
+        );
+        assert_eq!(
+            snooze_due(&config, &task, Snooze::Hours(3)),
+            Ok("2025-05-10 06:00".into())
+        );
+        assert_eq!(
+            snooze_due(&config, &task, Snooze::Tomorrow),
+            Ok("2025-05-11 05:00".into())
+        );
+        assert_eq!(
+            snooze_due(&config, &task, Snooze::Weekday),
+            Ok("2025-05-12 05:00".into())
+        );
+        assert_eq!(
+            snooze_due(&config, &task, Snooze::Week),
+            Ok("2025-05-12 05:00".into())
+        );
+        assert_eq!(
+            snooze_due(&config, &recurring, Snooze::Tomorrow),
+            Ok("2025-05-11".into())
+        );
     }
 
     #[tokio::test]
-    async fn test_deadline_value_when_tomorrow() {
-        let config = test::fixtures::config().await;
-        let task = test::fixtures::task(1).await;
+    async fn test_process_task_snooze() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "due_string": "2025-05-10 04:00"
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
 
-        let value = task.deadline_value(&config).unwrap();
-        assert_eq!(value, 120);
+        let task = test::fixtures::today_task().await;
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(6)
+            .with_mock_string("1")
+            .create()
+            .await
+            .unwrap();
+
+        let mut task_count = 3;
+        process_task(Vec::new(), &config, task, &mut task_count, true)
+            .await
+            .unwrap()
+            .unwrap()
+            .await
+            .unwrap();
+        mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
-    async fn test_deadline_value_when_in_six_days() {
-        let config = test::fixtures::config().await;
-        let task = test::fixtures::task(6).await;
+    async fn test_process_task_snooze_recurring() {
+        let mut server = mockito::Server::new_async().await;
+        // 19:00 in Vancouver tomorrow
+        let mock = server
+            .mock("POST", "/api/v1/sync")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "commands": [{
+                    "args": {
+                        "due": {"date": "2025-05-12T02:00:00Z"},
+                    },
+                }],
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!({"sync_status": {todoist::new_uuid(): "ok"}}).to_string())
+            .create_async()
+            .await;
 
-        let value = task.deadline_value(&config).unwrap();
-        assert_eq!(value, 0);
-    }
-    #[tokio::test]
+        let task = test::fixtures::today_task().await;
+        let task = Task {
+            due: Some(DateInfo {
+                is_recurring: true,
+                date: "2025-05-11T02:00:00Z".into(),
+                string: "every day at 7pm".into(),
+                ..task.due.clone().unwrap()
+            }),
+            ..task
+        };
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(3)
+            .create()
+            .await
+            .unwrap();
+
+        let mut task_count = 3;
+        process_task(Vec::new(), &config, task, &mut task_count, true)
+            .await
+            .unwrap()
+            .unwrap()
+            .await
+            .unwrap();
+        mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_triage_task() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .expect(0)
+            .create_async()
+            .await;
+        let task = test::fixtures::today_task().await;
+        // Quit
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(11);
+
+        let mut task_count = 1;
+        let mut handles = Vec::new();
+        let keep_going = triage_task(&config, task, &mut task_count, true, &mut handles)
+            .await
+            .unwrap();
+
+        assert!(!keep_going);
+        assert!(handles.is_empty());
+        assert_eq!(task_count, 0);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_triage_action_timebox() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "duration": 30,
+                "duration_unit": "minute",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+
+        let task = test::fixtures::today_task().await;
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .with_mock_string("30");
+
+        triage_action(&config, &task, input::TIMEBOX)
+            .await
+            .unwrap()
+            .unwrap()
+            .await
+            .unwrap();
+        mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_triage_action_timebox_recurring() {
+        let mut server = mockito::Server::new_async().await;
+        // Only the duration changes, so the schedule is kept
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "duration": 30,
+                "duration_unit": "minute",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+
+        let task = test::fixtures::today_task().await;
+        let task = Task {
+            due: Some(DateInfo {
+                is_recurring: true,
+                ..task.due.clone().unwrap()
+            }),
+            ..task
+        };
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .with_mock_string("30");
+
+        triage_action(&config, &task, input::TIMEBOX)
+            .await
+            .unwrap()
+            .unwrap()
+            .await
+            .unwrap();
+        mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_triage_action_move() {
+        let mut server = mockito::Server::new_async().await;
+        let sections_mock = server
+            .mock("GET", "/api/v1/sections?project_id=123&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Sections.read().await)
+            .create_async()
+            .await;
+        let move_mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8/move")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"project_id": "123"}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+
+        let task = test::fixtures::today_task().await;
+        // The project, then no section
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(0);
+
+        triage_action(&config, &task, input::MOVE)
+            .await
+            .unwrap()
+            .unwrap()
+            .await
+            .unwrap();
+        sections_mock.assert();
+        move_mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_triage_action_label() {
+        let mut server = mockito::Server::new_async().await;
+        let labels_mock = server
+            .mock("GET", "/api/v1/labels?limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Labels.read().await)
+            .create_async()
+            .await;
+        let update_mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "labels": ["computer", "345"],
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+
+        let task = test::fixtures::today_task().await;
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(0);
+
+        triage_action(&config, &task, input::LABEL)
+            .await
+            .unwrap()
+            .unwrap()
+            .await
+            .unwrap();
+        labels_mock.assert();
+        update_mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_triage_action_label_unchanged() {
+        let mut server = mockito::Server::new_async().await;
+        let labels_mock = server
+            .mock("GET", "/api/v1/labels?limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Labels.read().await)
+            .create_async()
+            .await;
+        let update_mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let task = Task {
+            labels: vec!["345".into()],
+            ..test::fixtures::today_task().await
+        };
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(0);
+
+        // Nothing to wait for, so the task is not fetched again
+        let handle = triage_action(&config, &task, input::LABEL).await.unwrap();
+        assert!(handle.is_none());
+        labels_mock.assert();
+        update_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_schedule_recurring_task_skips_occurrence() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8/close")
+            .with_status(204)
+            .create_async()
+            .await;
+        let due_mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let task = test::fixtures::today_task().await;
+        let task = Task {
+            due: Some(DateInfo {
+                is_recurring: true,
+                ..task.due.clone().unwrap()
+            }),
+            ..task
+        };
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(1);
+
+        spawn_schedule_task(config.clone(), task)
+            .await
+            .unwrap()
+            .unwrap()
+            .await
+            .unwrap();
+        mock.assert();
+        due_mock.assert();
+        crate::journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_schedule_recurring_task_skip() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let task = test::fixtures::today_task().await;
+        let task = Task {
+            due: Some(DateInfo {
+                is_recurring: true,
+                ..task.due.clone().unwrap()
+            }),
+            ..task
+        };
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .mock_select(3);
+
+        let handle = spawn_schedule_task(config, task).await.unwrap();
+        assert!(handle.is_none());
+        mock.assert();
+    }
+
+    #[test]
+    fn test_unit_reads_capitalized() {
+        let units: Vec<Unit> =
+            serde_json::from_str(r#"["minute", "Minute", "day", "Day"]"#).unwrap();
+        assert_eq!(
+            units,
+            vec![Unit::Minute, Unit::Minute, Unit::Day, Unit::Day]
+        );
+        assert_eq!(serde_json::to_string(&Unit::Day).unwrap(), r#""day""#);
+    }
+
+    #[tokio::test]
+    async fn test_display_task() {
+        let task = test::fixtures::today_task().await;
+        let string = String::from("TEST");
+        assert_eq!(string, task.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_has_due_time() {
+        let config = test::fixtures::config().await;
+        let task = test::fixtures::today_task().await;
+        assert_eq!(task.has_due_time(&config), Ok(true));
+
+        let date_only = Task {
+            due: Some(DateInfo {
+                date: "2025-05-10".into(),
+                ..task.due.clone().unwrap()
+            }),
+            ..task.clone()
+        };
+        assert_eq!(date_only.has_due_time(&config), Ok(false));
+
+        let no_due = Task { due: None, ..task };
+        assert_eq!(no_due.has_due_time(&config), Ok(false));
+    }
+
+    #[tokio::test]
+    async fn test_deadline_value_when_today() {
+        let config = test::fixtures::config().await;
+        let task = test::fixtures::today_task().await;
+        let value = task.deadline_value(&config).unwrap();
+        assert_eq!(value, 150);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_value_when_tomorrow() {
+        let config = test::fixtures::config().await;
+        let task = test::fixtures::task(1).await;
+
+        let value = task.deadline_value(&config).unwrap();
+        assert_eq!(value, 120);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_value_when_in_six_days() {
+        let config = test::fixtures::config().await;
+        let task = test::fixtures::task(6).await;
+
+        let value = task.deadline_value(&config).unwrap();
+        assert_eq!(value, 0);
+    }
+    #[tokio::test]
     async fn test_deadline_value_when_yesterday() {
         let config = test::fixtures::config().await;
         let task = test::fixtures::task(-1).await;
@@ -1474,4 +2454,314 @@ mod tests {
         let value = task.deadline_value(&config).unwrap();
         assert_eq!(value, 180);
     }
+
+    #[tokio::test]
+    async fn test_search() {
+        let base = test::fixtures::today_task().await;
+        let task = |content: &str| Task {
+            content: content.into(),
+            ..base.clone()
+        };
+        let tasks = vec![
+            task("Buy milk"),
+            task("Book dentist"),
+            task("Bake muffins"),
+            task("Call mom"),
+        ];
+
+        let contents = |term: &str| {
+            search(tasks.clone(), term)
+                .into_iter()
+                .map(|task| task.content)
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(contents("MILK"), vec!["Buy milk"]);
+        assert_eq!(contents("bm"), vec!["Buy milk", "Bake muffins"]);
+        assert_eq!(
+            contents("bk"),
+            vec!["Bake muffins", "Book dentist", "Buy milk"]
+        );
+        assert!(contents("xyz").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_show() {
+        let mut server = mockito::Server::new_async().await;
+        let task_mock = server
+            .mock("GET", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .expect(2)
+            .create_async()
+            .await;
+        let comments_mock = server
+            .mock(
+                "GET",
+                "/api/v1/comments/?task_id=6Xqhv4cwxgjwG9w8&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::CommentsAllTypes.read().await)
+            .expect(2)
+            .create_async()
+            .await;
+        let subtasks_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=6Xqhv4cwxgjwG9w8&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTasks.read().await)
+            .expect(2)
+            .create_async()
+            .await;
+        let completed_mock = server
+            .mock("GET", "/api/v1/tasks/completed/by_completion_date")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "parent_id".into(),
+                "6Xqhv4cwxgjwG9w8".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::CompletedTasks.read().await)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let output = show(&config, "6Xqhv4cwxgjwG9w8").await.unwrap();
+
+        assert!(output.starts_with("TEST\n"));
+        assert!(output.contains("Duration: 15 min\n"));
+        assert!(output.contains("\nSubtasks\n"));
+        assert!(output.contains("- [ ] "));
+        assert!(output.contains("- [x] Buy milk"));
+        assert!(output.contains("\nAttachments\n- file.pdf "));
+        assert!(output.contains("Comments"));
+
+        let config = config.with_output(crate::output::OutputFormat::Json);
+        let json = show(&config, "6Xqhv4cwxgjwG9w8").await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["id"], "6Xqhv4cwxgjwG9w8");
+        assert_eq!(parsed["section"], serde_json::Value::Null);
+        assert!(!parsed["subtasks"].as_array().unwrap().is_empty());
+        assert_eq!(parsed["comments"].as_array().unwrap().len(), 7);
+        task_mock.assert();
+        comments_mock.assert();
+        subtasks_mock.assert();
+        completed_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_render_details_truncated_comments() {
+        let mut config = test::fixtures::config().await;
+        config.max_comment_length = Some(20);
+        let task = test::fixtures::today_task().await;
+        let comments = vec![test::fixtures::comment(), test::fixtures::comment()];
+
+        let output = render_details(&config, &task, None, &[], comments)
+            .await
+            .unwrap();
+
+        assert!(
+            output
+                .ends_with("Showing part of 2 comment(s), use --output json to see all of them\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicate() {
+        let mut server = mockito::Server::new_async().await;
+        let task = test::fixtures::today_task().await;
+        let subtask = Task {
+            id: "subtask".into(),
+            parent_id: Some(task.id.clone()),
+            content: "Subtask".into(),
+            due: Some(DateInfo {
+                date: "2025-05-12".into(),
+                ..task.due.clone().unwrap()
+            }),
+            duration: Some(Duration {
+                amount: 2,
+                unit: Unit::Day,
+            }),
+            ..task.clone()
+        };
+        let new_task = |id: &str| {
+            serde_json::to_string(&Task {
+                id: id.into(),
+                ..task.clone()
+            })
+            .unwrap()
+        };
+        let task_mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "content": "TEST",
+                "project_id": "123",
+                "labels": ["computer"],
+                "priority": 3,
+                "due_datetime": "2025-05-10T12:00:00Z",
+                "deadline_date": task.deadline.as_ref().unwrap().date,
+                "duration": 15,
+                "duration_unit": "minute",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(new_task("new-parent"))
+            .create_async()
+            .await;
+        let subtask_mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "content": "Subtask",
+                "parent_id": "new-parent",
+                "due_date": "2025-05-12",
+                "duration": 2,
+                "duration_unit": "day",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(new_task("new-subtask"))
+            .create_async()
+            .await;
+        let subtasks_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=6Xqhv4cwxgjwG9w8&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!({"results": [subtask], "next_cursor": null}).to_string())
+            .create_async()
+            .await;
+        let no_subtasks_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=subtask&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"results": [], "next_cursor": null}"#)
+            .create_async()
+            .await;
+        let link = Comment {
+            file_attachment: serde_json::from_value(serde_json::json!({
+                "resource_type": "url",
+                "title": "Docs",
+                "url": "https://example.com/docs",
+            }))
+            .unwrap(),
+            ..test::fixtures::comment()
+        };
+        let comments_mock = server
+            .mock(
+                "GET",
+                "/api/v1/comments/?task_id=6Xqhv4cwxgjwG9w8&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({"results": [test::fixtures::comment(), link], "next_cursor": null})
+                    .to_string(),
+            )
+            .create_async()
+            .await;
+        let no_comments_mock = server
+            .mock("GET", "/api/v1/comments/?task_id=subtask&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"results": [], "next_cursor": null}"#)
+            .create_async()
+            .await;
+        let comment_mock = server
+            .mock("POST", "/api/v1/comments/")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "task_id": "new-parent",
+                "content": "Need one bottle of milk",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Comment.read().await)
+            .create_async()
+            .await;
+
+        let link_mock = server
+            .mock("POST", "/api/v1/comments/")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "task_id": "new-parent",
+                "attachment": {"url": "https://example.com/docs"},
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Comment.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let project = test::fixtures::project();
+        let tasks = duplicate(&config, &task, &project, true).await.unwrap();
+
+        assert_eq!(
+            tasks
+                .iter()
+                .map(|task| task.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["new-parent", "new-subtask"]
+        );
+        link_mock.assert();
+        task_mock.assert();
+        subtask_mock.assert();
+        subtasks_mock.assert();
+        no_subtasks_mock.assert();
+        comments_mock.assert();
+        no_comments_mock.assert();
+        comment_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_render_details() {
+        let config = test::fixtures::config().await.with_timezone("UTC");
+        let task = Task {
+            content: "Bake".into(),
+            project_id: test::fixtures::project().id,
+            description: String::new(),
+            labels: Vec::new(),
+            priority: Priority::None,
+            due: None,
+            deadline: Some(Deadline {
+                date: "2025-05-20".into(),
+                lang: "en".into(),
+            }),
+            duration: Some(Duration {
+                amount: 2,
+                unit: Unit::Day,
+            }),
+            ..test::fixtures::today_task().await
+        };
+        let subtask = |content: &str, child_order: i16, checked: bool| Task {
+            content: content.into(),
+            child_order,
+            checked,
+            parent_id: Some(task.id.clone()),
+            ..task.clone()
+        };
+        let subtasks = vec![subtask("Knead", 2, false), subtask("Mix", 1, true)];
+        let section = test::fixtures::section();
+
+        let output = render_details(&config, &task, Some(&section), &subtasks, Vec::new())
+            .await
+            .unwrap();
+        let url = format::maybe_format_task_id(&task.id, &config);
+
+        assert_eq!(
+            output,
+            format!(
+                "Bake\n\
+                # myproject {url}\n\
+                Section: {}\n\
+                Deadline: 2025-05-20\n\
+                Duration: 2 days\n\
+                \n\
+                Subtasks\n\
+                - [x] Mix\n\
+                - [ ] Knead\n",
+                section.name
+            )
+        );
+    }
 }
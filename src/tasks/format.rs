@@ -80,20 +80,9 @@ pub fn due(task: &Task, config: &Config, buffer: &str) -> String {
             };
             let datetime_string = time::datetime_to_string(datetime, config).unwrap_or_default();
 
-            let duration_string = match task.duration {
+            let duration_string = match &task.duration {
                 None => String::new(),
-                Some(Duration {
-                    amount: 1,
-                    unit: Unit::Day,
-                }) => " for 1 day".into(),
-                Some(Duration {
-                    amount,
-                    unit: Unit::Day,
-                }) => format!(" for {amount} days"),
-                Some(Duration {
-                    amount,
-                    unit: Unit::Minute,
-                }) => format!(" for {amount} min"),
+                Some(task_duration) => format!(" for {}", duration(task_duration)),
             };
 
             format!("\n{buffer}{due_icon} {datetime_string}{duration_string}{recurring_icon}")
@@ -103,6 +92,24 @@ pub fn due(task: &Task, config: &Config, buffer: &str) -> String {
     }
 }
 
+/// i.e. "1 day", "3 days" or "15 min"
+pub fn duration(duration: &Duration) -> String {
+    match duration {
+        Duration {
+            amount: 1,
+            unit: Unit::Day,
+        } => "1 day".into(),
+        Duration {
+            amount,
+            unit: Unit::Day,
+        } => format!("{amount} days"),
+        Duration {
+            amount,
+            unit: Unit::Minute,
+        } => format!("{amount} min"),
+    }
+}
+
 // Formats a string for all style/formatted links (including markdown) and formats them as a hyperlink
 fn create_links(content: &str) -> String {
     // Define the regex pattern for Markdown links
@@ -133,9 +140,14 @@ pub fn number_comments(quantity: usize) -> String {
 
     format!("\n{comment_icon} {quantity} comments")
 }
+/// Link to a task in the Todoist web app
+pub fn task_url(task_id: &str) -> String {
+    format!("https://app.todoist.com/app/task/{task_id}")
+}
+
 /// Returns a hyperlink-formatted URL formatted as "[link]" for a given task ID if hyperlinks are enabled in the config.
 pub fn maybe_format_task_id(task_id: &str, config: &Config) -> String {
-    let url = format!("https://app.todoist.com/app/task/{task_id}");
+    let url = task_url(task_id);
     if hyperlinks_disabled(config) {
         url
     } else {
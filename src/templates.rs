@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+
+use chrono::Duration;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    errors::Error,
+    projects::Project,
+    sections,
+    tasks::{Task, priority},
+    time, todoist,
+};
+
+static VARIABLE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*([\w-]+)\s*\}\}").unwrap());
+
+/// A named bundle of tasks in the config, created with `tod task template`
+#[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Template {
+    /// Can use variables such as "Release {{version}}"
+    pub content: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// From 1 (none) to 4 (highest)
+    pub priority: Option<u8>,
+    /// Name of the project in the config, prompts for a project when neither this nor --project is used.
+    /// Only for the top task, children go in the project of their parent
+    pub project: Option<String>,
+    /// Name of a section in the project, only for the top task
+    pub section: Option<String>,
+    /// Either relative to today such as "+2d" or "+1w", or anything Todoist understands such as "tomorrow 9am"
+    pub due: Option<String>,
+    /// Created as subtasks of this task
+    #[serde(default)]
+    pub children: Vec<Template>,
+}
+
+/// Parse variables given as "key=value"
+pub fn parse_vars(vars: &[String]) -> Result<HashMap<String, String>, Error> {
+    vars.iter()
+        .map(|var| match var.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.to_string()))
+            }
+            _ => Err(Error::new(
+                "parse_vars",
+                &format!("Variable '{var}' must be in the form key=value"),
+            )),
+        })
+        .collect()
+}
+
+/// Create the task in the template and all of its children, returning them in the order created
+pub async fn create(
+    config: &Config,
+    template: &Template,
+    project: &Project,
+    vars: &HashMap<String, String>,
+) -> Result<Vec<Task>, Error> {
+    // Check everything first so that a mistake does not leave half of the tasks created
+    validate(config, template, vars, false)?;
+
+    let mut created = Vec::new();
+    // Depth first so that every parent exists before its children
+    let mut stack = vec![(template, None)];
+    while let Some((template, parent_id)) = stack.pop() {
+        let task = create_task(config, template, project, vars, parent_id).await?;
+        for child in template.children.iter().rev() {
+            stack.push((child, Some(task.id.clone())));
+        }
+        created.push(task);
+    }
+    Ok(created)
+}
+
+fn validate(
+    config: &Config,
+    template: &Template,
+    vars: &HashMap<String, String>,
+    child: bool,
+) -> Result<(), Error> {
+    // Subtasks always go in the project and section of their parent
+    if child && (template.project.is_some() || template.section.is_some()) {
+        return Err(Error::new(
+            "template",
+            &format!(
+                "'{}' is a subtask and can't have its own project or section",
+                template.content
+            ),
+        ));
+    }
+    fill(&template.content, vars)?;
+    fill(template.description.as_deref().unwrap_or_default(), vars)?;
+    for label in &template.labels {
+        fill(label, vars)?;
+    }
+    if let Some(number) = template.priority.filter(|number| !(1..=4).contains(number)) {
+        return Err(Error::new(
+            "template",
+            &format!(
+                "Priority {number} for '{}' must be from 1 to 4",
+                template.content
+            ),
+        ));
+    }
+    if let Some(due) = &template.due {
+        due_string(config, due)?;
+    }
+    template
+        .children
+        .iter()
+        .try_for_each(|child| validate(config, child, vars, true))
+}
+
+async fn create_task(
+    config: &Config,
+    template: &Template,
+    project: &Project,
+    vars: &HashMap<String, String>,
+    parent_id: Option<String>,
+) -> Result<Task, Error> {
+    let content = fill(&template.content, vars)?;
+    let description = fill(template.description.as_deref().unwrap_or_default(), vars)?;
+    let labels = template
+        .labels
+        .iter()
+        .map(|label| fill(label, vars))
+        .collect::<Result<Vec<String>, Error>>()?;
+    let priority = priority::from_integer(&template.priority).unwrap_or(priority::Priority::None);
+    let due = template
+        .due
+        .as_deref()
+        .map(|due| due_string(config, due))
+        .transpose()?;
+    // Subtasks are placed in the section of their parent
+    let section = match (&template.section, &parent_id) {
+        (Some(name), None) => Some(sections::find_section(config, project, name).await?),
+        _ => None,
+    };
+
+    todoist::create_task(
+        config,
+        &content,
+        project,
+        section,
+        priority,
+        &description,
+        due.as_deref(),
+        &labels,
+        parent_id.as_deref(),
+        None,
+        None,
+    )
+    .await
+}
+
+/// Replace variables such as "{{version}}", erroring on any that were not given
+fn fill(text: &str, vars: &HashMap<String, String>) -> Result<String, Error> {
+    let mut missing = Vec::new();
+    let filled = VARIABLE_REGEX.replace_all(text, |captures: &regex::Captures| {
+        let key = &captures[1];
+        match vars.get(key) {
+            Some(value) => value.clone(),
+            None => {
+                missing.push(key.to_string());
+                captures[0].to_string()
+            }
+        }
+    });
+
+    if missing.is_empty() {
+        Ok(filled.into_owned())
+    } else {
+        Err(Error::new(
+            "template",
+            &format!(
+                "Missing variable(s) {}, use --var {}=VALUE",
+                missing.join(", "),
+                missing[0]
+            ),
+        ))
+    }
+}
+
+/// Turns a relative due date such as "+2d" or "+1w" into a date, and passes anything else through
+fn due_string(config: &Config, due: &str) -> Result<String, Error> {
+    let Some(relative) = due.trim().strip_prefix('+') else {
+        return Ok(due.to_string());
+    };
+    let invalid = || {
+        Error::new(
+            "template",
+            &format!(
+                "Relative due date '{due}' must be a number of days or weeks, i.e. +2d or +1w"
+            ),
+        )
+    };
+    let unit = relative.chars().last().ok_or_else(invalid)?;
+    let amount = relative[..relative.len() - unit.len_utf8()]
+        .parse::<i64>()
+        .map_err(|_| invalid())?;
+    let days = match unit {
+        'd' => amount,
+        'w' => amount * 7,
+        _ => return Err(invalid()),
+    };
+    let date = time::naive_date_today(config)? + Duration::days(days);
+    Ok(date.format(time::FORMAT_DATE).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{self, responses::ResponseFromFile};
+    use mockito::Matcher;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn vars() -> HashMap<String, String> {
+        HashMap::from([("version".to_string(), "1.2.0".to_string())])
+    }
+
+    fn template() -> Template {
+        Template {
+            content: "Release {{version}}".into(),
+            description: Some("Ship {{ version }} to crates.io".into()),
+            labels: vec!["release".into()],
+            priority: Some(3),
+            project: None,
+            section: None,
+            due: Some("+1w".into()),
+            children: vec![Template {
+                content: "Update the changelog for {{version}}".into(),
+                description: None,
+                labels: Vec::new(),
+                priority: None,
+                project: None,
+                section: None,
+                due: None,
+                children: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_vars() {
+        assert_eq!(parse_vars(&["version=1.2.0".into()]), Ok(vars()));
+        assert_eq!(
+            parse_vars(&["url=https://x.com/?a=b".into()]),
+            Ok(HashMap::from([(
+                "url".to_string(),
+                "https://x.com/?a=b".to_string()
+            )]))
+        );
+        assert!(parse_vars(&["version".into()]).is_err());
+        assert!(parse_vars(&["=1.2.0".into()]).is_err());
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            fill("Release {{version}} and {{ version }}", &vars()),
+            Ok("Release 1.2.0 and 1.2.0".into())
+        );
+        assert_eq!(
+            fill("No variables", &HashMap::new()),
+            Ok("No variables".into())
+        );
+        assert_eq!(
+            fill("Release {{version}} on {{day}}", &vars()),
+            Err(Error::new(
+                "template",
+                "Missing variable(s) day, use --var day=VALUE"
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_due_string() {
+        let config = test::fixtures::config().await;
+        assert_eq!(due_string(&config, "+2d"), Ok("2025-05-12".into()));
+        assert_eq!(due_string(&config, "+1w"), Ok("2025-05-17".into()));
+        assert_eq!(
+            due_string(&config, "every monday"),
+            Ok("every monday".into())
+        );
+        assert!(due_string(&config, "+2m").is_err());
+        assert!(due_string(&config, "+d").is_err());
+        assert!(due_string(&config, "+").is_err());
+        assert!(due_string(&config, "+2日").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create() {
+        let mut server = mockito::Server::new_async().await;
+        let parent_mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .match_body(Matcher::PartialJson(json!({
+                "content": "Release 1.2.0",
+                "description": "Ship 1.2.0 to crates.io",
+                "labels": ["release"],
+                "priority": 3,
+                "due_date": "2025-05-17",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+        let child_mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .match_body(Matcher::PartialJson(json!({
+                "content": "Update the changelog for 1.2.0",
+                "parent_id": "6Xqhv4cwxgjwG9w8",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let project = test::fixtures::project();
+        let tasks = create(&config, &template(), &project, &vars())
+            .await
+            .unwrap();
+
+        assert_eq!(tasks.len(), 2);
+        parent_mock.assert();
+        child_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_create_checks_before_creating() {
+        let config = test::fixtures::config().await;
+        let project = test::fixtures::project();
+        let mut template = template();
+        template.children[0].priority = Some(5);
+
+        let result = create(&config, &template, &project, &vars()).await;
+        assert_eq!(
+            result,
+            Err(Error::new(
+                "template",
+                "Priority 5 for 'Update the changelog for {{version}}' must be from 1 to 4"
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_project_on_subtask() {
+        let config = test::fixtures::config().await;
+        let project = test::fixtures::project();
+        let mut template = template();
+        template.children[0].section = Some("Bread".into());
+
+        let result = create(&config, &template, &project, &vars()).await;
+        assert_eq!(
+            result,
+            Err(Error::new(
+                "template",
+                "'Update the changelog for {{version}}' is a subtask and can't have its own project or section"
+            ))
+        );
+    }
+}
@@ -173,7 +173,8 @@ pub fn comment() -> Comment {
         uids_to_notify: None,
         posted_at: "2016-09-22T07:00:00.000000Z".to_string(),
         reactions: None,
-        item_id: "123".to_string(),
+        item_id: Some("123".to_string()),
+        project_id: None,
         is_deleted: false,
         file_attachment: None,
     }
@@ -18,6 +18,8 @@ pub enum ResponseFromFile {
     TodayTask,
     Ids,
     TodayTasks,
+    /// Tasks completed on 2025-05-09 and 2025-05-10
+    CompletedTasks,
     Comment,
     #[allow(dead_code)]
     Label,
@@ -28,6 +30,8 @@ pub enum ResponseFromFile {
     NewProjects,
     Section,
     Sections,
+    /// A file uploaded for a comment attachment
+    Upload,
     /// Data about the logged in user
     User,
     /// Response from crates.io API
@@ -63,8 +67,10 @@ impl ResponseFromFile {
         let replace_with: Vec<(&str, String)> = match self {
             Self::AccessToken => Vec::new(),
             Self::CommentsAllTypes => Vec::new(),
+            Self::Upload => Vec::new(),
             Self::Comment => Vec::new(),
             Self::Task => Vec::new(),
+            Self::CompletedTasks => Vec::new(),
             Self::Ids => Vec::new(),
             Self::Section => Vec::new(),
             Self::Sections => Vec::new(),
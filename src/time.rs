@@ -1,14 +1,14 @@
 use crate::config::Config;
 use crate::errors::Error;
 
-use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc, Weekday};
 use chrono_tz::Tz;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::str::FromStr;
 
 pub const FORMAT_DATE: &str = "%Y-%m-%d";
-const FORMAT_TIME: &str = "%H:%M";
+pub const FORMAT_TIME: &str = "%H:%M";
 const FORMAT_DATETIME: &str = "%Y-%m-%dT%H:%M:%S";
 const FORMAT_DATETIME_ZULU: &str = "%Y-%m-%dT%H:%M:%SZ";
 const FORMAT_DATETIME_LONG: &str = "%Y-%m-%dT%H:%M:%S%.fZ";
@@ -111,6 +111,23 @@ fn parse_datetime(str: &str, timezone: Tz, format: &str) -> Result<DateTime<Tz>,
     naive_datetime_to_datetime(naive_datetime, timezone)
 }
 
+/// The same local time of day on another date, i.e. to postpone a task due at 17:00 in its own timezone
+pub fn same_time_on_date(
+    datetime: DateTime<Tz>,
+    date: NaiveDate,
+    timezone: Tz,
+) -> Result<DateTime<Tz>, Error> {
+    let local = datetime.with_timezone(&timezone);
+    naive_datetime_to_datetime(date.and_time(local.time()), timezone)
+}
+
+/// Midnight at the start of a date in the given timezone
+pub fn start_of_day(date: NaiveDate, timezone: Tz) -> Result<DateTime<Tz>, Error> {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|datetime| datetime.and_local_timezone(timezone).earliest())
+        .ok_or_else(|| Error::new("start_of_day", &format!("Invalid date: {date}")))
+}
+
 fn naive_datetime_to_datetime(
     datetime: NaiveDateTime,
     timezone: Tz,
@@ -207,6 +224,20 @@ pub fn date_string_today(config: &Config) -> Result<String, Error> {
     Ok(today.format(FORMAT_DATE).to_string())
 }
 
+/// The next day from Monday to Friday after the date
+pub fn next_weekday(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Fri => date + Duration::days(3),
+        Weekday::Sat => date + Duration::days(2),
+        _ => date + Duration::days(1),
+    }
+}
+
+/// The Monday after the date
+pub fn next_monday(date: NaiveDate) -> NaiveDate {
+    date + Duration::days(7 - i64::from(date.weekday().num_days_from_monday()))
+}
+
 // Formats a date to a string
 pub fn date_to_string(date: &NaiveDate, config: &Config) -> Result<String, Error> {
     if is_date_today(*date, config)? {
@@ -265,6 +296,20 @@ mod tests {
     use super::*;
     use chrono_tz::Tz;
 
+    #[test]
+    fn test_next_weekday_and_monday() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 5, day).unwrap();
+        // Thursday, Friday, Saturday and Sunday
+        assert_eq!(next_weekday(date(8)), date(9));
+        assert_eq!(next_weekday(date(9)), date(12));
+        assert_eq!(next_weekday(date(10)), date(12));
+        assert_eq!(next_weekday(date(11)), date(12));
+
+        assert_eq!(next_monday(date(10)), date(12));
+        assert_eq!(next_monday(date(11)), date(12));
+        assert_eq!(next_monday(date(12)), date(19));
+    }
+
     #[test]
     fn test_is_date() {
         assert!(is_date("2022-10-05"));
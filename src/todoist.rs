@@ -1,41 +1,56 @@
+use chrono::{Duration, NaiveDate, Utc};
 use futures::future;
 use serde_json::{Number, Value, json};
 use std::collections::HashMap;
+use std::path::Path;
 use urlencoding::encode;
 mod request;
 
-use crate::comments::{Comment, CommentResponse};
+#[cfg(test)]
+pub use request::new_uuid;
+
+use crate::comments::{Attachment, Comment, CommentResponse, FileAttachment};
 use crate::config::Config;
 use crate::debug::maybe_print;
 use crate::errors::Error;
 use crate::id::{self, Resource};
+use crate::journal::{self, Action};
 use crate::labels::{self, Label, LabelResponse};
 use crate::oauth::{CLIENT_ID, CLIENT_SECRET};
 use crate::projects::{Project, ProjectResponse};
 use crate::sections::{Section, SectionResponse};
 use crate::shell::execute_command;
 use crate::tasks::priority::Priority;
-use crate::tasks::{Task, TaskResponse};
+use crate::tasks::{CompletedTaskResponse, DateInfo, Task, TaskResponse, Unit};
 use crate::users;
 use crate::users::User;
-use crate::{color, projects, sections, tasks, time};
+use crate::{color, projects, sections, tasks, time, trash};
 use crate::{comments, oauth};
 use regex::Regex;
 
 // TODOIST URLS
 pub const TASKS_URL: &str = "/api/v1/tasks/";
 pub const COMMENTS_URL: &str = "/api/v1/comments/";
+const COMPLETED_TASKS_URL: &str = "/api/v1/tasks/completed/by_completion_date";
 const SECTIONS_URL: &str = "/api/v1/sections";
 const USER_URL: &str = "/api/v1/user";
 const PROJECTS_URL: &str = "/api/v1/projects";
 const LABELS_URL: &str = "/api/v1/labels";
 const IDS_URL: &str = "/api/v1/id_mappings/";
+const UPLOADS_URL: &str = "/api/v1/uploads";
+const SYNC_URL: &str = "/api/v1/sync";
 const ACCESS_TOKEN_URL: &str = "/oauth/access_token";
 pub const OAUTH_URL: &str = "/oauth/authorize";
 
+/// Datetimes sent as query parameters
+const FORMAT_DATETIME_UTC: &str = "%Y-%m-%dT%H:%M:%SZ";
+
 /// Number of items that can be requested from API at once
 pub const QUERY_LIMIT: u8 = 200;
 
+/// Completed tasks can only be requested for up to 3 months at a time
+const COMPLETED_TASKS_MAX_DAYS: i64 = 89;
+
 /// Used to sanity check all the Todoist API endpoints to make sure that we are able to process the JSON payloads they are sending back.
 pub async fn test_all_endpoints(config: Config) -> Result<String, Error> {
     let name = "TEST".to_string();
@@ -62,6 +77,9 @@ pub async fn test_all_endpoints(config: Config) -> Result<String, Error> {
         &name,
         None,
         &[],
+        None,
+        None,
+        None,
     )
     .await?;
 
@@ -194,6 +212,9 @@ pub async fn create_task(
     description: &str,
     due: Option<&str>,
     labels: &[String],
+    parent_id: Option<&str>,
+    deadline: Option<&str>,
+    duration: Option<u32>,
 ) -> Result<Task, Error> {
     let project_id = project.id.clone();
     let url = TASKS_URL.into();
@@ -225,6 +246,28 @@ pub async fn create_task(
         body.insert("section_id".to_owned(), Value::String(section.id.clone()));
     }
 
+    if let Some(parent_id) = parent_id {
+        body.insert("parent_id".to_owned(), Value::String(parent_id.to_owned()));
+    }
+
+    if let Some(date) = deadline {
+        if !time::is_date(date) {
+            return Err(Error::new(
+                "create_task",
+                &format!("Deadline must be in format YYYY-MM-DD, got: {date}"),
+            ));
+        }
+        body.insert("deadline_date".to_owned(), Value::String(date.to_owned()));
+    }
+
+    if let Some(duration) = duration {
+        body.insert("duration".to_owned(), Value::Number(Number::from(duration)));
+        body.insert(
+            "duration_unit".to_owned(),
+            Value::String("minute".to_owned()),
+        );
+    }
+
     let body = json!(body);
 
     let json = request::post_todoist(config, url, body, true).await?;
@@ -232,6 +275,32 @@ pub async fn create_task(
     tasks::json_to_task(json)
 }
 
+/// All active tasks across every project
+pub async fn all_tasks(config: &Config) -> Result<Vec<Task>, Error> {
+    let mut tasks = Vec::new();
+    let mut url = format!("{TASKS_URL}?limit={QUERY_LIMIT}");
+    let title_regex = config.task_exclude_regex.as_ref();
+
+    loop {
+        let json = request::get_todoist(config, url, true).await?;
+        let TaskResponse {
+            results,
+            next_cursor,
+        } = tasks::json_to_tasks_response(json)?;
+
+        let results = filter_tasks_by_title(results, title_regex, config);
+        tasks.extend(results);
+
+        match next_cursor {
+            None => break,
+            Some(cursor) => {
+                url = format!("{TASKS_URL}?limit={QUERY_LIMIT}&cursor={cursor}");
+            }
+        }
+    }
+    Ok(tasks)
+}
+
 /// Get a vector of all tasks for a project
 pub async fn all_tasks_by_project(
     config: &Config,
@@ -264,6 +333,32 @@ pub async fn all_tasks_by_project(
     Ok(tasks)
 }
 
+/// All active subtasks of a task, not including their own subtasks
+pub async fn all_subtasks(config: &Config, task: &Task) -> Result<Vec<Task>, Error> {
+    let parent_id = &task.id;
+    let mut subtasks = Vec::new();
+    let mut url = format!("{TASKS_URL}?parent_id={parent_id}&limit={QUERY_LIMIT}");
+
+    loop {
+        let json = request::get_todoist(config, url, true).await?;
+        let TaskResponse {
+            results,
+            next_cursor,
+        } = tasks::json_to_tasks_response(json)?;
+        subtasks.extend(results);
+
+        match next_cursor {
+            None => break,
+            Some(cursor) => {
+                url = format!(
+                    "{TASKS_URL}?parent_id={parent_id}&limit={QUERY_LIMIT}&cursor={cursor}"
+                );
+            }
+        }
+    }
+    Ok(subtasks)
+}
+
 /// Uses multiple filters (comma-separated) to fetch multiple lists of tasks in parallel. Returns each list of tasks with the filter query that was used to find it.
 pub async fn all_tasks_by_filters(
     config: &Config,
@@ -315,6 +410,97 @@ pub async fn all_tasks_by_filter(
     Ok((filter.to_string(), tasks))
 }
 
+/// Tasks completed on the days from since to until inclusive, for a project, a filter, or everything when neither is given
+pub async fn all_completed_tasks(
+    config: &Config,
+    since: NaiveDate,
+    until: NaiveDate,
+    project: Option<&Project>,
+    filter: Option<&str>,
+) -> Result<Vec<Task>, Error> {
+    let mut params = String::new();
+    if let Some(project) = project {
+        params.push_str(&format!("&project_id={}", project.id));
+    }
+    if let Some(filter) = filter {
+        params.push_str(&format!("&filter_query={}", encode(filter)));
+    }
+    completed_tasks_in_ranges(config, since, until, &params).await
+}
+
+/// Subtasks of a task that were completed at any time since the task was added
+pub async fn all_completed_subtasks(config: &Config, task: &Task) -> Result<Vec<Task>, Error> {
+    let tz = time::timezone_from_str(&config.get_timezone()?)?;
+    let until = time::naive_date_today(config)?;
+    let since = task
+        .added_at
+        .as_ref()
+        .and_then(|added_at| time::datetime_from_str(added_at, tz).ok())
+        .map(|added_at| added_at.with_timezone(&tz).date_naive())
+        .unwrap_or(until - Duration::days(COMPLETED_TASKS_MAX_DAYS - 1));
+    let params = format!("&parent_id={}", task.id);
+    completed_tasks_in_ranges(config, since, until, &params).await
+}
+
+/// Completed tasks from since to until inclusive, fetched in ranges short enough for Todoist to accept
+async fn completed_tasks_in_ranges(
+    config: &Config,
+    since: NaiveDate,
+    until: NaiveDate,
+    params: &str,
+) -> Result<Vec<Task>, Error> {
+    let mut tasks: Vec<Task> = Vec::new();
+    let mut start = since;
+    while start <= until {
+        let end = until.min(start + Duration::days(COMPLETED_TASKS_MAX_DAYS - 1));
+        tasks.extend(completed_tasks_between(config, start, end, params).await?);
+        start = end + Duration::days(1);
+    }
+    Ok(tasks)
+}
+
+/// A single range of completed_tasks_in_ranges, params are appended to the query, i.e. "&project_id=123"
+async fn completed_tasks_between(
+    config: &Config,
+    since: NaiveDate,
+    until: NaiveDate,
+    params: &str,
+) -> Result<Vec<Task>, Error> {
+    let tz = time::timezone_from_str(&config.get_timezone()?)?;
+    let since = time::start_of_day(since, tz)?.with_timezone(&Utc);
+    let until = time::start_of_day(until + Duration::days(1), tz)?.with_timezone(&Utc);
+    let query = format!(
+        "since={}&until={}&limit={QUERY_LIMIT}{params}",
+        encode(&since.format(FORMAT_DATETIME_UTC).to_string()),
+        encode(&until.format(FORMAT_DATETIME_UTC).to_string())
+    );
+
+    let mut tasks: Vec<Task> = Vec::new();
+    let mut url = format!("{COMPLETED_TASKS_URL}?{query}");
+
+    loop {
+        let json = request::get_todoist(config, url, true).await?;
+        let CompletedTaskResponse { items, next_cursor } =
+            tasks::json_to_completed_tasks_response(json)?;
+        tasks.extend(items);
+
+        match next_cursor {
+            None => break,
+            Some(string) => {
+                url = format!("{COMPLETED_TASKS_URL}?{query}&cursor={string}");
+            }
+        };
+    }
+
+    Ok(tasks)
+}
+
+pub async fn get_section(config: &Config, id: &str) -> Result<Section, Error> {
+    let url = format!("{SECTIONS_URL}/{id}");
+    let json = request::get_todoist(config, url, true).await?;
+    sections::json_to_section(json)
+}
+
 pub async fn all_sections_by_project(
     config: &Config,
     project: &Project,
@@ -403,6 +589,7 @@ pub async fn move_task_to_project(
     let url = format!("{TASKS_URL}{task_id}/move");
 
     let response = request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Move, task).await;
     tasks::json_to_task(response)
 }
 
@@ -418,6 +605,22 @@ pub async fn move_task_to_section(
     let url = format!("{TASKS_URL}{task_id}/move");
 
     let response = request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Move, task).await;
+    tasks::json_to_task(response)
+}
+
+/// Move a task under another task, making it a subtask
+pub async fn move_task_to_parent(
+    config: &Config,
+    task: &Task,
+    parent: &Task,
+    spinner: bool,
+) -> Result<Task, Error> {
+    let body = json!({"parent_id": parent.id});
+    let url = format!("{TASKS_URL}{}/move", task.id);
+
+    let response = request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Move, task).await;
     tasks::json_to_task(response)
 }
 
@@ -432,6 +635,7 @@ pub async fn update_task_priority(
     let url = format!("{}{}", TASKS_URL, task.id);
 
     request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Update, task).await;
     // Does not pass back an task
     Ok("✓".into())
 }
@@ -443,12 +647,13 @@ pub async fn add_task_label(
     label: String,
     spinner: bool,
 ) -> Result<String, Error> {
-    let mut labels = task.labels;
+    let mut labels = task.labels.clone();
     labels.push(label);
     let body = json!({ "labels": labels});
     let url = format!("{}{}", TASKS_URL, task.id);
 
     request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Update, &task).await;
     // Does not pass back an task
     Ok("✓".into())
 }
@@ -479,6 +684,23 @@ pub async fn update_task_due_natural_language(
     let url = format!("{}{}", TASKS_URL, task.id);
 
     request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Update, task).await;
+    // Does not pass back a task
+    Ok("✓".into())
+}
+
+/// Update the duration of a task in minutes by ID
+pub async fn update_task_duration(
+    config: &Config,
+    task: &Task,
+    duration: u32,
+    spinner: bool,
+) -> Result<String, Error> {
+    let body = json!({ "duration": duration, "duration_unit": "minute" });
+    let url = format!("{}{}", TASKS_URL, task.id);
+
+    request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Update, task).await;
     // Does not pass back a task
     Ok("✓".into())
 }
@@ -494,6 +716,7 @@ pub async fn update_task_content(
     let url = format!("{}{}", TASKS_URL, task.id);
 
     request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Update, task).await;
     // Does not pass back a task
     Ok("✓".into())
 }
@@ -520,6 +743,7 @@ pub async fn update_task_deadline(
     let url = format!("{}{}", TASKS_URL, task.id);
 
     request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Update, task).await;
     // Does not pass back a task
     Ok("✓".into())
 }
@@ -535,6 +759,7 @@ pub async fn update_task_description(
     let url = format!("{}{}", TASKS_URL, task.id);
 
     request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Update, task).await;
     // Does not pass back a task
     Ok("✓".into())
 }
@@ -551,21 +776,26 @@ pub async fn update_task_labels(
     let url = format!("{}{}", TASKS_URL, task.id);
 
     request::post_todoist(config, url, body, spinner).await?;
+    record(config, Action::Update, task).await;
     // Does not pass back a task
     Ok("✓".into())
 }
 
-/// Complete the last task returned by "next task"
+/// Complete a task, forgetting it as the "next task" if it was one
 /// The API does not return any data, so we can't return a new task
 pub async fn complete_task(config: &Config, task: &Task, spinner: bool) -> Result<String, Error> {
     let task_id = task.id.clone();
     let url = format!("{TASKS_URL}{task_id}/close");
 
     request::post_todoist(config, url, Value::Null, spinner).await?;
+    record(config, Action::Complete, task).await;
 
     if !cfg!(test) {
         maybe_run_command(config.task_complete_command.as_deref()).await;
-        config.reload().await?.clear_next_task().save().await?;
+        let config = config.reload().await?;
+        if config.next_task().is_some_and(|next| next.id == task_id) {
+            config.clear_next_task().save().await?;
+        }
     }
     // Execute the execute_command() complete_task_command if set in config
 
@@ -573,14 +803,256 @@ pub async fn complete_task(config: &Config, task: &Task, spinner: bool) -> Resul
     Ok("✓".into())
 }
 
+/// Move only the current occurrence of a recurring task to another date, keeping its recurrence.
+/// The REST endpoints replace the recurrence when given a date, so this goes through sync.
+pub async fn postpone_occurrence(
+    config: &Config,
+    task: &Task,
+    date: &str,
+    spinner: bool,
+) -> Result<String, Error> {
+    let due = recurring_due(task, "postpone_occurrence")?;
+    let date = postponed_date(config, due, date)?;
+    let due = json!({
+        "date": date,
+        "string": due.string,
+        "lang": due.lang,
+        "is_recurring": true,
+    });
+
+    sync_update_due(config, task, due, spinner).await?;
+    record(config, Action::Update, task).await;
+    Ok("✓".into())
+}
+
+/// The due date on another day, keeping the time of day where the user is
+fn postponed_date(config: &Config, due: &DateInfo, date: &str) -> Result<String, Error> {
+    let date = time::date_string_to_naive_date(date)?;
+    if time::is_date(&due.date) {
+        Ok(date.format(time::FORMAT_DATE).to_string())
+    } else if due.date.ends_with('Z') {
+        // Times in UTC can fall on a different day than they do locally
+        let tz = time::timezone_from_str(&config.get_timezone()?)?;
+        let datetime = time::datetime_from_str(&due.date, tz)?;
+        let postponed = time::same_time_on_date(datetime, date, tz)?;
+        Ok(postponed
+            .with_timezone(&Utc)
+            .format(FORMAT_DATETIME_UTC)
+            .to_string())
+    } else {
+        // A floating time is already local, i.e. "T17:00:00"
+        let time = due.date.find('T').map(|index| &due.date[index..]);
+        Ok(format!("{date}{}", time.unwrap_or_default()))
+    }
+}
+
+/// Set the due date through sync, which unlike the REST endpoints accepts a full due object
+async fn sync_update_due(
+    config: &Config,
+    task: &Task,
+    due: Value,
+    spinner: bool,
+) -> Result<(), Error> {
+    let uuid = request::new_uuid();
+    let body = json!({
+        "commands": [{
+            "type": "item_update",
+            "uuid": uuid,
+            "args": {
+                "id": task.id,
+                "due": due,
+            },
+        }],
+    });
+
+    let json = request::post_todoist(config, SYNC_URL.into(), body, spinner).await?;
+    let response: Value = serde_json::from_str(&json)?;
+    match &response["sync_status"][&uuid] {
+        Value::String(status) if status == "ok" => Ok(()),
+        status => Err(Error::new(
+            "sync_update_due",
+            &format!(
+                "Could not update the due date of '{}': {status}",
+                task.content
+            ),
+        )),
+    }
+}
+
+/// Move a recurring task to its next occurrence. Closing a recurring task does this instead of completing it.
+pub async fn skip_occurrence(config: &Config, task: &Task, spinner: bool) -> Result<String, Error> {
+    recurring_due(task, "skip_occurrence")?;
+    let url = format!("{TASKS_URL}{}/close", task.id);
+
+    request::post_todoist(config, url, Value::Null, spinner).await?;
+    // Undone like completing a recurring task, by putting back its due date
+    record(config, Action::Complete, task).await;
+    // API does not pass back a task
+    Ok("✓".into())
+}
+
+/// Closing or postponing a task that does not recur would lose it, so check first
+fn recurring_due<'a>(task: &'a Task, source: &str) -> Result<&'a DateInfo, Error> {
+    match &task.due {
+        Some(due) if due.is_recurring => Ok(due),
+        _ => Err(Error::new(
+            source,
+            &format!("'{}' is not a recurring task", task.content),
+        )),
+    }
+}
+
+/// Reopen a completed task
+/// The API does not return any data, so we can't return a new task
+pub async fn reopen_task(config: &Config, task: &Task, spinner: bool) -> Result<String, Error> {
+    let url = format!("{}{}/reopen", TASKS_URL, task.id);
+
+    request::post_todoist(config, url, Value::Null, spinner).await?;
+    Ok("✓".into())
+}
+
+/// Delete a task, keeping a copy in the local trash so that it can be restored
 pub async fn delete_task(config: &Config, task: &Task, spinner: bool) -> Result<String, Error> {
+    // Comments and subtasks can no longer be fetched once the task is gone
+    let trashed = trash::collect(config, task).await?;
     let body = json!({});
     let url = format!("{}{}", TASKS_URL, task.id);
 
     request::delete_todoist(config, url, body, spinner).await?;
+    // The trash holds the task for undo, so undo only needs to know when it was deleted
+    match trash::add(config, trashed).await {
+        Ok(()) => record(config, Action::Delete, task).await,
+        Err(e) => eprintln!("Could not keep '{}' in the trash: {e}", task.content),
+    }
     Ok("✓".into())
 }
 
+/// Put back every field of a task that has changed since it was recorded
+pub async fn restore_task(config: &Config, prior: &Task) -> Result<(), Error> {
+    let current = get_task(config, &prior.id).await?;
+    let mut body = serde_json::Map::new();
+    if current.content != prior.content {
+        body.insert("content".into(), json!(prior.content));
+    }
+    if current.description != prior.description {
+        body.insert("description".into(), json!(prior.description));
+    }
+    if current.priority != prior.priority {
+        body.insert("priority".into(), json!(prior.priority));
+    }
+    if current.labels != prior.labels {
+        body.insert("labels".into(), json!(prior.labels));
+    }
+    if current.deadline != prior.deadline {
+        let deadline = prior.deadline.as_ref();
+        body.insert("deadline_date".into(), json!(deadline.map(|d| &d.date)));
+        body.insert("deadline_lang".into(), json!(deadline.map(|d| &d.lang)));
+    }
+    if current.duration != prior.duration {
+        let duration = prior.duration.as_ref();
+        body.insert("duration".into(), json!(duration.map(|d| d.amount)));
+        body.insert(
+            "duration_unit".into(),
+            json!(duration.map(|d| duration_unit(&d.unit))),
+        );
+    }
+    if current.due != prior.due && prior.due.is_none() {
+        body.insert("due_string".into(), json!("no date"));
+    }
+
+    if !body.is_empty() {
+        let url = format!("{}{}", TASKS_URL, prior.id);
+        request::post_todoist(config, url, Value::Object(body), true).await?;
+    }
+
+    match &prior.due {
+        Some(due) if current.due.as_ref() != Some(due) => {
+            let due = json!({
+                "date": due.date,
+                "string": due.string,
+                "lang": due.lang,
+                "is_recurring": due.is_recurring,
+                "timezone": due.timezone,
+            });
+            sync_update_due(config, prior, due, true).await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Create a new task with the same fields as another, i.e. to bring back a deleted task or to copy one
+pub async fn recreate_task(config: &Config, prior: &Task) -> Result<Task, Error> {
+    let mut body = json!({
+        "content": prior.content,
+        "description": prior.description,
+        "project_id": prior.project_id,
+        "priority": prior.priority,
+        "labels": prior.labels,
+    });
+
+    if let Some(section_id) = &prior.section_id {
+        body["section_id"] = json!(section_id);
+    }
+    if let Some(parent_id) = &prior.parent_id {
+        body["parent_id"] = json!(parent_id);
+    }
+    // Recurring tasks need the due string to keep recurring, everything else keeps its exact date
+    if let Some(due) = &prior.due {
+        let (key, value) = if due.is_recurring {
+            ("due_string", due.string.clone())
+        } else if time::is_date(&due.date) {
+            ("due_date", due.date.clone())
+        } else if due.date.ends_with('Z') {
+            ("due_datetime", due.date.clone())
+        } else {
+            // A floating time without a timezone, i.e. "2025-05-10T12:00:00"
+            ("due_string", due.date.replace('T', " "))
+        };
+        body[key] = json!(value);
+    }
+    if let Some(deadline) = &prior.deadline {
+        body["deadline_date"] = json!(deadline.date);
+    }
+    if let Some(duration) = &prior.duration {
+        body["duration"] = json!(duration.amount);
+        body["duration_unit"] = json!(duration_unit(&duration.unit));
+    }
+
+    let json = request::post_todoist(config, TASKS_URL.into(), body, true).await?;
+    tasks::json_to_task(json)
+}
+
+/// Move a task back under its parent, section or project, whichever it had
+pub async fn move_task_back(config: &Config, prior: &Task) -> Result<Task, Error> {
+    let body = match (&prior.parent_id, &prior.section_id) {
+        (Some(parent_id), _) => json!({ "parent_id": parent_id }),
+        (None, Some(section_id)) => json!({ "section_id": section_id }),
+        (None, None) => json!({ "project_id": prior.project_id }),
+    };
+    let url = format!("{TASKS_URL}{}/move", prior.id);
+
+    let json = request::post_todoist(config, url, body, true).await?;
+    tasks::json_to_task(json)
+}
+
+fn duration_unit(unit: &Unit) -> &'static str {
+    match unit {
+        Unit::Minute => "minute",
+        Unit::Day => "day",
+    }
+}
+
+/// Remember a task as it was before a change so that it can be undone.
+/// The change has already been made by then, so a journal that cannot be written only warns.
+async fn record(config: &Config, action: Action, task: &Task) {
+    if let Err(e) = journal::record(config, action, task).await {
+        eprintln!(
+            "Could not record the {action} of '{}' for undo: {e}",
+            task.content
+        );
+    }
+}
+
 pub async fn delete_project(
     config: &Config,
     project: &Project,
@@ -634,6 +1106,62 @@ pub async fn create_comment(
     comments::json_to_comment(response)
 }
 
+/// Comment on a task with a file that was uploaded with upload_file
+pub async fn create_comment_with_attachment(
+    config: &Config,
+    task: &Task,
+    content: String,
+    attachment: &Attachment,
+    spinner: bool,
+) -> Result<Comment, Error> {
+    let body = json!({"task_id": task.id, "content": content, "attachment": attachment});
+    let url = COMMENTS_URL.to_string();
+
+    let response = request::post_todoist(config, url, body, spinner).await?;
+    maybe_run_command(config.task_comment_command.as_deref()).await;
+    comments::json_to_comment(response)
+}
+
+/// Upload a local file so that it can be attached to a comment
+pub async fn upload_file(config: &Config, path: &Path) -> Result<FileAttachment, Error> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| Error::new("upload_file", &format!("Not a file: {}", path.display())))?;
+    let contents = tokio::fs::read(path).await?;
+    let file_type = comments::file_type(path);
+
+    let json = request::upload_todoist(
+        config,
+        UPLOADS_URL.into(),
+        file_name,
+        file_type,
+        contents,
+        true,
+    )
+    .await?;
+    let attachment: FileAttachment = serde_json::from_str(&json)?;
+    Ok(attachment)
+}
+
+/// Download an attachment, i.e. a file uploaded to a comment
+pub async fn download_file(config: &Config, url: &str) -> Result<Vec<u8>, Error> {
+    request::download(config, url, true).await
+}
+
+pub async fn create_project_comment(
+    config: &Config,
+    project: &Project,
+    content: String,
+    spinner: bool,
+) -> Result<Comment, Error> {
+    let body = json!({"project_id": project.id, "content": content});
+    let url = COMMENTS_URL.to_string();
+
+    let response = request::post_todoist(config, url, body, spinner).await?;
+    comments::json_to_comment(response)
+}
+
 pub async fn get_user_data(config: &Config) -> Result<User, Error> {
     let url = USER_URL.to_string();
     let json = request::get_todoist(config, url, true).await?;
@@ -648,9 +1176,25 @@ pub async fn all_comments(
     task: &Task,
     limit: Option<u8>,
 ) -> Result<Vec<Comment>, Error> {
-    let task_id = &task.id;
+    comments_by(config, &format!("task_id={}", task.id), limit).await
+}
+
+pub async fn all_project_comments(
+    config: &Config,
+    project: &Project,
+    limit: Option<u8>,
+) -> Result<Vec<Comment>, Error> {
+    comments_by(config, &format!("project_id={}", project.id), limit).await
+}
+
+/// Comments that are not deleted or excluded by config, for a query such as "task_id=123"
+async fn comments_by(
+    config: &Config,
+    query: &str,
+    limit: Option<u8>,
+) -> Result<Vec<Comment>, Error> {
     let limit = limit.unwrap_or(QUERY_LIMIT);
-    let mut url = format!("{COMMENTS_URL}?task_id={task_id}&limit={limit}");
+    let mut url = format!("{COMMENTS_URL}?{query}&limit={limit}");
     let mut comments: Vec<Comment> = Vec::new();
 
     let exclude_regex = config.comment_exclude_regex.as_ref();
@@ -673,8 +1217,7 @@ pub async fn all_comments(
         match next_cursor {
             None => break,
             Some(cursor) => {
-                url =
-                    format!("{COMMENTS_URL}?task_id={task_id}&limit={QUERY_LIMIT}&cursor={cursor}");
+                url = format!("{COMMENTS_URL}?{query}&limit={QUERY_LIMIT}&cursor={cursor}");
             }
         };
     }
@@ -682,6 +1225,37 @@ pub async fn all_comments(
     Ok(comments)
 }
 
+pub async fn get_comment(config: &Config, id: &str) -> Result<Comment, Error> {
+    let url = format!("{COMMENTS_URL}{id}");
+    let json = request::get_todoist(config, url, true).await?;
+    comments::json_to_comment(json)
+}
+
+/// Replace the content of a task or project comment
+pub async fn update_comment(
+    config: &Config,
+    comment: &Comment,
+    content: String,
+    spinner: bool,
+) -> Result<Comment, Error> {
+    let body = json!({ "content": content });
+    let url = format!("{}{}", COMMENTS_URL, comment.id);
+
+    let response = request::post_todoist(config, url, body, spinner).await?;
+    comments::json_to_comment(response)
+}
+
+pub async fn delete_comment(
+    config: &Config,
+    comment: &Comment,
+    spinner: bool,
+) -> Result<String, Error> {
+    let url = format!("{}{}", COMMENTS_URL, comment.id);
+
+    request::delete_todoist(config, url, json!({}), spinner).await?;
+    Ok("✓".into())
+}
+
 // Executes a CLI command (if set in the configuration).
 async fn maybe_run_command(command: Option<&str>) {
     if let Some(command) = command {
@@ -817,13 +1391,76 @@ mod tests {
                 priority,
                 "",
                 None,
-                &[]
+                &[],
+                None,
+                None,
+                None,
             )
             .await,
             Ok(test::fixtures::today_task().await)
         );
         mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_create_subtask_with_deadline_and_duration() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "parent_id": "6Xqhv4cwxgjwG9w8",
+                "deadline_date": "2025-05-20",
+                "duration": 30,
+                "duration_unit": "minute",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTask.read().await)
+            .create_async()
+            .await;
+        let config = test::fixtures::config()
+            .await
+            .with_mock_url(server.url())
+            .with_time_provider(TimeProviderEnum::Fixed(FixedTimeProvider));
+        let project = test::fixtures::project();
+
+        let result = create_task(
+            &config,
+            "New subtask",
+            &project,
+            None,
+            Priority::None,
+            "",
+            Some("today 3pm"),
+            &[],
+            Some("6Xqhv4cwxgjwG9w8"),
+            Some("2025-05-20"),
+            Some(30),
+        )
+        .await;
+        assert!(result.is_ok());
+        mock.assert();
+
+        let result = create_task(
+            &config,
+            "New subtask",
+            &project,
+            None,
+            Priority::None,
+            "",
+            None,
+            &[],
+            None,
+            Some("next week"),
+            None,
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().message,
+            "Deadline must be in format YYYY-MM-DD, got: next week"
+        );
+    }
+
     #[tokio::test]
     async fn test_create_section() {
         let mut server = mockito::Server::new_async().await;
@@ -909,6 +1546,149 @@ mod tests {
         let response = complete_task(&config, &task, false).await.unwrap();
         mock.assert();
         assert_eq!(response, String::from("✓"));
+        journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_reopen_task() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8/reopen")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+
+        let task = test::fixtures::today_task().await;
+        let response = reopen_task(&config, &task, false).await.unwrap();
+        mock.assert();
+        assert_eq!(response, String::from("✓"));
+    }
+
+    #[tokio::test]
+    async fn test_postpone_occurrence() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/sync")
+            .match_body(mockito::Matcher::Json(json!({
+                "commands": [{
+                    "type": "item_update",
+                    "uuid": request::new_uuid(),
+                    "args": {
+                        "id": "6Xqhv4cwxgjwG9w8",
+                        "due": {
+                            "date": "2025-05-12T12:00:00Z",
+                            "string": "every day at 5am",
+                            "lang": "en",
+                            "is_recurring": true,
+                        },
+                    },
+                }],
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"sync_status": {request::new_uuid(): "ok"}}).to_string())
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+        let task = Task {
+            due: Some(tasks::DateInfo {
+                is_recurring: true,
+                string: "every day at 5am".into(),
+                ..task.due.clone().unwrap()
+            }),
+            ..task
+        };
+
+        let response = postpone_occurrence(&config, &task, "2025-05-12", false).await;
+        assert_eq!(response, Ok(String::from("✓")));
+        mock.assert();
+
+        let not_recurring = test::fixtures::today_task().await;
+        assert_eq!(
+            postpone_occurrence(&config, &not_recurring, "2025-05-12", false).await,
+            Err(Error::new(
+                "postpone_occurrence",
+                "'TEST' is not a recurring task"
+            ))
+        );
+        journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_skip_occurrence() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/tasks/6Xqhv4cwxgjwG9w8/close")
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+        let recurring = Task {
+            due: Some(tasks::DateInfo {
+                is_recurring: true,
+                ..task.due.clone().unwrap()
+            }),
+            ..task.clone()
+        };
+
+        assert_eq!(
+            skip_occurrence(&config, &recurring, false).await,
+            Ok(String::from("✓"))
+        );
+        // Closing a task that does not recur would complete it
+        assert_eq!(
+            skip_occurrence(&config, &task, false).await,
+            Err(Error::new(
+                "skip_occurrence",
+                "'TEST' is not a recurring task"
+            ))
+        );
+        mock.assert();
+        journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_postpone_occurrence_keeps_local_time() {
+        let mut server = mockito::Server::new_async().await;
+        // 19:00 in Vancouver is already the next day in UTC
+        let mock = server
+            .mock("POST", "/api/v1/sync")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "commands": [{
+                    "args": {
+                        "due": {"date": "2025-05-13T02:00:00Z"},
+                    },
+                }],
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"sync_status": {request::new_uuid(): "ok"}}).to_string())
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+        let task = Task {
+            due: Some(tasks::DateInfo {
+                is_recurring: true,
+                date: "2025-05-11T02:00:00Z".into(),
+                string: "every day at 7pm".into(),
+                ..task.due.clone().unwrap()
+            }),
+            ..task
+        };
+
+        let response = postpone_occurrence(&config, &task, "2025-05-12", false).await;
+        assert_eq!(response, Ok(String::from("✓")));
+        mock.assert();
+        journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
@@ -937,6 +1717,7 @@ mod tests {
 
         assert_eq!(response, task);
         mock.assert();
+        journal::JOURNAL.remove(&config).await;
     }
     #[tokio::test]
     async fn test_move_task_to_section() {
@@ -963,6 +1744,7 @@ mod tests {
 
         assert_eq!(response, task);
         mock.assert();
+        journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
@@ -976,14 +1758,61 @@ mod tests {
             .with_body(ResponseFromFile::TodayTask.read().await)
             .create_async()
             .await;
+        // Comments that cannot be fetched do not stop the delete
+        let comments_mock = server
+            .mock(
+                "GET",
+                "/api/v1/comments/?task_id=6Xqhv4cwxgjwG9w8&limit=200",
+            )
+            .with_status(500)
+            .create_async()
+            .await;
+        let subtasks_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=6Xqhv4cwxgjwG9w8&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"results": [], "next_cursor": null}).to_string())
+            .create_async()
+            .await;
 
         let task = test::fixtures::today_task().await;
         let config = test::fixtures::config().await.with_mock_url(server.url());
 
         let response = delete_task(&config, &task, false).await;
         mock.assert();
+        comments_mock.assert();
+        subtasks_mock.assert();
 
         assert_eq!(response, Ok(String::from("✓")));
+        let trashed: Vec<trash::TrashedTask> = trash::TRASH.load(&config).await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].task, task);
+        assert_eq!(trashed[0].comments, Vec::new());
+        trash::TRASH.remove(&config).await;
+        journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_delete_task_without_subtasks_to_keep() {
+        let mut server = mockito::Server::new_async().await;
+        // Subtasks are deleted along with the task, so it is kept when they cannot be
+        let subtasks_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=6Xqhv4cwxgjwG9w8&limit=200")
+            .with_status(500)
+            .create_async()
+            .await;
+        let mock = server
+            .mock("DELETE", "/api/v1/tasks/6Xqhv4cwxgjwG9w8")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let task = test::fixtures::today_task().await;
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+
+        assert!(delete_task(&config, &task, false).await.is_err());
+        subtasks_mock.assert();
+        mock.assert();
     }
 
     #[tokio::test]
@@ -1007,6 +1836,43 @@ mod tests {
         assert_eq!(response.project_id, String::from("6VRRxv8CM6GVmmgf"));
     }
 
+    #[tokio::test]
+    async fn test_get_section() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/sections/1234")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Section.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+
+        let section = get_section(&config, "1234").await.unwrap();
+        mock.assert();
+        assert_eq!(section.name, "Bread");
+    }
+
+    #[tokio::test]
+    async fn test_all_subtasks() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=6Xqhv4cwxgjwG9w8&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::TodayTasks.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+
+        let subtasks = all_subtasks(&config, &task).await.unwrap();
+        mock.assert();
+        assert_eq!(subtasks.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_forbidden() {
         let mut server = mockito::Server::new_async().await;
@@ -1051,6 +1917,30 @@ mod tests {
         let response = update_task_priority(&config, &task, &Priority::High, true).await;
         mock.assert();
         assert_eq!(response, Ok(String::from("✓")));
+        journal::JOURNAL.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_update_task_duration() {
+        let task = test::fixtures::today_task().await;
+        let url: &str = &format!("{}{}", "/api/v1/tasks/", task.id);
+        let mut server = mockito::Server::new_async().await;
+
+        let mock = server
+            .mock("POST", url)
+            .match_body(mockito::Matcher::Json(
+                json!({"duration": 45, "duration_unit": "minute"}),
+            ))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+
+        let response = update_task_duration(&config, &task, 45, false).await;
+        mock.assert();
+        assert_eq!(response, Ok(String::from("✓")));
+        journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
@@ -1073,6 +1963,7 @@ mod tests {
             update_task_due_natural_language(&config, &task, "today".to_string(), None, true).await;
         mock.assert();
         assert_eq!(response, Ok(String::from("✓")));
+        journal::JOURNAL.remove(&config).await;
     }
 
     #[tokio::test]
@@ -1100,6 +1991,133 @@ mod tests {
         assert_eq!(comments.len(), 7); // One comment in the JSON is_deleted = true
         assert!(comments.iter().all(|c| !c.is_deleted));
     }
+
+    #[tokio::test]
+    async fn test_upload_file() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/uploads")
+            .match_header(
+                "content-type",
+                mockito::Matcher::Regex("^multipart/form-data; boundary=".into()),
+            )
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::Regex(
+                    "name=\"file\"; filename=\"import_tasks.txt\"\r\nContent-Type: text/plain"
+                        .into(),
+                ),
+                mockito::Matcher::Regex("Email quarterly report to manager".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Upload.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+
+        let attachment = upload_file(&config, Path::new("tests/inputs/import_tasks.txt"))
+            .await
+            .unwrap();
+        mock.assert();
+        assert_eq!(attachment.file_name, "import_tasks.txt");
+        assert_eq!(attachment.resource_type, "file");
+    }
+
+    #[tokio::test]
+    async fn test_create_comment_with_attachment() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/comments/")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "task_id": "6Xqhv4cwxgjwG9w8",
+                "content": "Crash log",
+                "attachment": {
+                    "file_name": "import_tasks.txt",
+                    "file_type": "text/plain",
+                    "file_url": "https://files.todoist.com/uploads/import_tasks.txt",
+                    "resource_type": "file"
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Comment.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let task = test::fixtures::today_task().await;
+        let attachment: FileAttachment =
+            serde_json::from_str(&ResponseFromFile::Upload.read().await).unwrap();
+
+        let response = create_comment_with_attachment(
+            &config,
+            &task,
+            "Crash log".into(),
+            &Attachment::File(attachment),
+            false,
+        )
+        .await;
+        mock.assert();
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_all_project_comments() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/comments/?project_id=123&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::CommentsAllTypes.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let project = test::fixtures::project();
+
+        let comments = all_project_comments(&config, &project, None).await.unwrap();
+        mock.assert();
+        assert_eq!(comments.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_update_comment() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/comments/2992679862")
+            .match_body(mockito::Matcher::Json(json!({"content": "Two bottles"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Comment.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let comment = test::fixtures::comment();
+
+        let response = update_comment(&config, &comment, "Two bottles".into(), false).await;
+        mock.assert();
+        assert_eq!(response, Ok(comment));
+    }
+
+    #[tokio::test]
+    async fn test_delete_comment() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("DELETE", "/api/v1/comments/2992679862")
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let comment = test::fixtures::comment();
+
+        let response = delete_comment(&config, &comment, false).await;
+        mock.assert();
+        assert_eq!(response, Ok("✓".into()));
+    }
+
     #[tokio::test]
     async fn test_task_is_filtered_out_by_regex() {
         let mut task = test::fixtures::today_task().await;
@@ -1128,4 +2146,38 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].content, task.content);
     }
+
+    #[tokio::test]
+    async fn test_all_completed_tasks_splits_long_ranges() {
+        let mut server = mockito::Server::new_async().await;
+        let first_mock = server
+            .mock(
+                "GET",
+                "/api/v1/tasks/completed/by_completion_date?since=2025-01-01T08%3A00%3A00Z&until=2025-03-31T07%3A00%3A00Z&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::CompletedTasks.read().await)
+            .create_async()
+            .await;
+        let second_mock = server
+            .mock(
+                "GET",
+                "/api/v1/tasks/completed/by_completion_date?since=2025-03-31T07%3A00%3A00Z&until=2025-05-11T07%3A00%3A00Z&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::CompletedTasks.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        let since = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let until = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let result = all_completed_tasks(&config, since, until, None, None).await;
+
+        assert_eq!(result.map(|tasks| tasks.len()), Ok(6));
+        first_mock.assert();
+        second_mock.assert();
+    }
 }
@@ -0,0 +1,385 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    color, comments::Comment, config::Config, errors::Error, journal, store::Store, tasks,
+    tasks::Task, time, todoist,
+};
+
+/// Only the most recently deleted tasks are kept, i.e. $XDG_CONFIG_HOME/tod.trash
+pub static TRASH: Store = Store::new("trash", 200);
+
+/// A task deleted from Todoist, kept so that it can be created again
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct TrashedTask {
+    pub task: Task,
+    pub comments: Vec<Comment>,
+    /// Todoist deletes subtasks at every depth along with the task, parents come before their children
+    #[serde(default)]
+    pub subtasks: Vec<Subtask>,
+    /// In format YYYY-MM-DD HH:MM
+    pub deleted_at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Subtask {
+    pub task: Task,
+    pub comments: Vec<Comment>,
+}
+
+/// A task created again from the trash, along with the old and new IDs of it and its subtasks
+pub struct Restored {
+    pub task: Task,
+    pub ids: Vec<(String, String)>,
+}
+
+/// Fetch everything that is deleted along with a task, while it can still be fetched
+pub async fn collect(config: &Config, task: &Task) -> Result<TrashedTask, Error> {
+    let deleted_at = time::datetime_now(config)?
+        .format(time::FORMAT_DATE_AND_TIME)
+        .to_string();
+    let mut subtasks = Vec::new();
+    let mut parents = vec![task.clone()];
+    while let Some(parent) = parents.pop() {
+        for subtask in todoist::all_subtasks(config, &parent).await? {
+            parents.push(subtask.clone());
+            subtasks.push(Subtask {
+                comments: comments(config, &subtask).await,
+                task: subtask,
+            });
+        }
+    }
+
+    Ok(TrashedTask {
+        task: task.clone(),
+        comments: comments(config, task).await,
+        subtasks,
+        deleted_at,
+    })
+}
+
+/// Comments are worth keeping, but not worth keeping a task for
+async fn comments(config: &Config, task: &Task) -> Vec<Comment> {
+    todoist::all_comments(config, task, None)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("Could not keep the comments of '{}': {e}", task.content);
+            Vec::new()
+        })
+}
+
+/// Keep a deleted task along with its comments and subtasks
+pub async fn add(config: &Config, trashed: TrashedTask) -> Result<(), Error> {
+    TRASH.push(config, trashed).await
+}
+
+/// Deleted tasks, most recent first
+pub async fn list(config: &Config) -> Result<String, Error> {
+    let trashed: Vec<TrashedTask> = TRASH.load(config).await?;
+    if trashed.is_empty() {
+        return Ok(color::green_string("Trash is empty"));
+    }
+
+    let lines = trashed
+        .iter()
+        .rev()
+        .map(
+            |TrashedTask {
+                 task, deleted_at, ..
+             }| {
+                format!(
+                    "{} {} {}",
+                    color::purple_string(&task.id),
+                    task.content,
+                    color::normal_string(&format!("(deleted {deleted_at})"))
+                )
+            },
+        )
+        .collect::<Vec<String>>();
+    Ok(format!(
+        "{}\n{}",
+        color::green_string("Trash"),
+        lines.join("\n")
+    ))
+}
+
+/// Create a deleted task again in its original project and section, along with its comments and subtasks
+pub async fn restore(config: &Config, id: &str) -> Result<String, Error> {
+    let Restored { task, ids } = restore_deleted(config, id).await?;
+    // The task is back with a new ID, so undo should not bring it back a second time
+    if let Err(e) = journal::restored(config, id, &ids).await {
+        eprintln!("Could not update undo for '{}': {e}", task.content);
+    }
+
+    let subtasks = match ids.len() - 1 {
+        0 => String::new(),
+        count => format!(" and {count} subtask(s)"),
+    };
+    Ok(color::green_string(&format!(
+        "Restored '{}' with ID {}{subtasks}",
+        task.content, task.id
+    )))
+}
+
+/// Create a deleted task and its subtasks again, and take it out of the trash
+pub async fn restore_deleted(config: &Config, id: &str) -> Result<Restored, Error> {
+    let _lock = TRASH.lock().await;
+    let mut trashed: Vec<TrashedTask> = TRASH.load(config).await?;
+    let index = trashed
+        .iter()
+        .position(|trashed| trashed.task.id == id)
+        .ok_or_else(|| {
+            Error::new(
+                "trash",
+                &format!("No task with ID {id} in the trash, use trash list to see deleted tasks"),
+            )
+        })?;
+
+    let TrashedTask {
+        task,
+        comments,
+        subtasks,
+        ..
+    } = trashed[index].clone();
+    let restored = todoist::recreate_task(config, &task).await?;
+    // Forget the task before adding comments and subtasks so that a failure does not restore it twice
+    trashed.remove(index);
+    TRASH.save(config, &trashed).await?;
+    restore_comments(config, comments, &restored).await;
+
+    let mut ids = vec![(task.id, restored.id.clone())];
+    for Subtask { task, comments } in subtasks {
+        // Subtasks go under the copy of their parent, or under the copy of the task if their parent could not be restored
+        let parent_id = ids
+            .iter()
+            .find(|(old_id, _)| Some(old_id) == task.parent_id.as_ref())
+            .map_or(&restored.id, |(_, new_id)| new_id);
+        let subtask = Task {
+            parent_id: Some(parent_id.clone()),
+            ..task.clone()
+        };
+        match todoist::recreate_task(config, &subtask).await {
+            Ok(created) => {
+                restore_comments(config, comments, &created).await;
+                ids.push((task.id, created.id));
+            }
+            Err(e) => eprintln!("Could not restore the subtask '{}': {e}", task.content),
+        }
+    }
+
+    Ok(Restored {
+        task: restored,
+        ids,
+    })
+}
+
+/// The task itself is back by now, so missing comments only warn
+async fn restore_comments(config: &Config, comments: Vec<Comment>, task: &Task) {
+    if let Err(e) = tasks::copy_comments(config, comments, task).await {
+        eprintln!("Could not restore the comments of '{}': {e}", task.content);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::{Action, JOURNAL};
+    use crate::test::{self, responses::ResponseFromFile};
+    use mockito::Matcher;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn subtask(task: &Task) -> Task {
+        Task {
+            id: "subtask".into(),
+            parent_id: Some(task.id.clone()),
+            content: "Subtask".into(),
+            ..task.clone()
+        }
+    }
+
+    fn tasks_response(tasks: Vec<Task>) -> String {
+        json!({"results": tasks, "next_cursor": null}).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_collect_add_and_list() {
+        let mut server = mockito::Server::new_async().await;
+        let task = test::fixtures::today_task().await;
+        let subtask = subtask(&task);
+        let subtasks_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=6Xqhv4cwxgjwG9w8&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(tasks_response(vec![subtask.clone()]))
+            .create_async()
+            .await;
+        let nested_mock = server
+            .mock("GET", "/api/v1/tasks/?parent_id=subtask&limit=200")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(tasks_response(Vec::new()))
+            .create_async()
+            .await;
+        let comments_mock = server
+            .mock(
+                "GET",
+                "/api/v1/comments/?task_id=6Xqhv4cwxgjwG9w8&limit=200",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({"results": [test::fixtures::comment()], "next_cursor": null}).to_string(),
+            )
+            .create_async()
+            .await;
+        // Comments that cannot be fetched do not stop the delete
+        let subtask_comments_mock = server
+            .mock("GET", "/api/v1/comments/?task_id=subtask&limit=200")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        assert_eq!(
+            list(&config).await,
+            Ok(color::green_string("Trash is empty"))
+        );
+
+        let trashed = collect(&config, &task).await.unwrap();
+        subtasks_mock.assert();
+        nested_mock.assert();
+        comments_mock.assert();
+        subtask_comments_mock.assert();
+        assert_eq!(
+            trashed,
+            TrashedTask {
+                task: task.clone(),
+                comments: vec![test::fixtures::comment()],
+                subtasks: vec![Subtask {
+                    task: subtask,
+                    comments: Vec::new(),
+                }],
+                deleted_at: "2025-05-10 03:00".into(),
+            }
+        );
+
+        add(&config, trashed.clone()).await.unwrap();
+        assert_eq!(TRASH.load(&config).await, Ok(vec![trashed]));
+        assert_eq!(
+            list(&config).await,
+            Ok(format!(
+                "{}\n{} TEST {}",
+                color::green_string("Trash"),
+                color::purple_string("6Xqhv4cwxgjwG9w8"),
+                color::normal_string("(deleted 2025-05-10 03:00)")
+            ))
+        );
+        TRASH.remove(&config).await;
+    }
+
+    #[tokio::test]
+    async fn test_restore() {
+        let mut server = mockito::Server::new_async().await;
+        let task = test::fixtures::today_task().await;
+        let subtask = subtask(&task);
+        // Todoist gives the copies new IDs
+        let task_mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .match_body(Matcher::PartialJson(json!({
+                "content": "TEST",
+                "project_id": "6VRRxv8CM6GVmmgf",
+                "labels": ["computer"],
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!(Task {
+                    id: "new-task".into(),
+                    ..task.clone()
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let subtask_mock = server
+            .mock("POST", "/api/v1/tasks/")
+            .match_body(Matcher::PartialJson(json!({
+                "content": "Subtask",
+                "parent_id": "new-task",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!(Task {
+                    id: "new-subtask".into(),
+                    ..subtask.clone()
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let comment_mock = server
+            .mock("POST", "/api/v1/comments/")
+            .match_body(Matcher::Json(json!({
+                "task_id": "new-task",
+                "content": "Need one bottle of milk",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ResponseFromFile::Comment.read().await)
+            .create_async()
+            .await;
+
+        let config = test::fixtures::config().await.with_mock_url(server.url());
+        journal::record(&config, Action::Update, &task)
+            .await
+            .unwrap();
+        journal::record(&config, Action::Update, &subtask)
+            .await
+            .unwrap();
+        journal::record(&config, Action::Delete, &task)
+            .await
+            .unwrap();
+        let trashed = TrashedTask {
+            task: task.clone(),
+            comments: vec![test::fixtures::comment()],
+            subtasks: vec![Subtask {
+                task: subtask,
+                comments: Vec::new(),
+            }],
+            deleted_at: "2025-05-10 03:00".into(),
+        };
+        add(&config, trashed).await.unwrap();
+
+        assert_eq!(
+            restore(&config, "6Xqhv4cwxgjwG9w8").await,
+            Ok(color::green_string(
+                "Restored 'TEST' with ID new-task and 1 subtask(s)"
+            ))
+        );
+        task_mock.assert();
+        subtask_mock.assert();
+        comment_mock.assert();
+        assert_eq!(TRASH.load::<TrashedTask>(&config).await, Ok(Vec::new()));
+        // Undo does not restore the task a second time, and older changes apply to the copies
+        let entries: Vec<journal::Entry> = JOURNAL.load(&config).await.unwrap();
+        let ids: Vec<(&str, Option<&str>)> = entries
+            .iter()
+            .map(|entry| (entry.task.id.as_str(), entry.task.parent_id.as_deref()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![("new-task", None), ("new-subtask", Some("new-task"))]
+        );
+
+        assert_eq!(
+            restore(&config, "6Xqhv4cwxgjwG9w8").await,
+            Err(Error::new(
+                "trash",
+                "No task with ID 6Xqhv4cwxgjwG9w8 in the trash, use trash list to see deleted tasks"
+            ))
+        );
+        TRASH.remove(&config).await;
+        JOURNAL.remove(&config).await;
+    }
+}